@@ -5,6 +5,22 @@ mod m20251109_032731_app_schema;
 mod m20251207_021458_decimal_and_object_grid_schema;
 mod m20251207_041152_drop_event_object_grid;
 mod m20251207_042647_change_time_to_utc;
+mod m20251214_101500_workspace_member;
+mod m20251216_094500_unique_event_object_position;
+mod m20251218_083000_reservation_expiry_index;
+mod m20251220_120000_form_submission;
+mod m20251222_090000_decimal_money;
+mod m20251224_100000_event_series;
+mod m20251226_080000_event_timezone;
+mod m20251228_090000_soft_delete;
+mod m20251230_090000_event_status;
+mod m20260102_090000_section_sale_window;
+mod m20260104_090000_promo_code;
+mod m20260106_090000_reservation_payment;
+mod m20260108_090000_payment_event;
+mod m20260110_090000_refund;
+mod m20260112_090000_ticket;
+mod m20260114_090000_check_in;
 
 pub struct Migrator;
 
@@ -17,6 +33,22 @@ impl MigratorTrait for Migrator {
             Box::new(m20251207_021458_decimal_and_object_grid_schema::Migration),
             Box::new(m20251207_041152_drop_event_object_grid::Migration),
             Box::new(m20251207_042647_change_time_to_utc::Migration),
+            Box::new(m20251214_101500_workspace_member::Migration),
+            Box::new(m20251216_094500_unique_event_object_position::Migration),
+            Box::new(m20251218_083000_reservation_expiry_index::Migration),
+            Box::new(m20251220_120000_form_submission::Migration),
+            Box::new(m20251222_090000_decimal_money::Migration),
+            Box::new(m20251224_100000_event_series::Migration),
+            Box::new(m20251226_080000_event_timezone::Migration),
+            Box::new(m20251228_090000_soft_delete::Migration),
+            Box::new(m20251230_090000_event_status::Migration),
+            Box::new(m20260102_090000_section_sale_window::Migration),
+            Box::new(m20260104_090000_promo_code::Migration),
+            Box::new(m20260106_090000_reservation_payment::Migration),
+            Box::new(m20260108_090000_payment_event::Migration),
+            Box::new(m20260110_090000_refund::Migration),
+            Box::new(m20260112_090000_ticket::Migration),
+            Box::new(m20260114_090000_check_in::Migration),
         ]
     }
 }
@@ -0,0 +1,121 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkspaceMember::Table)
+                    .if_not_exists()
+                    .col(uuid(WorkspaceMember::Id).primary_key())
+                    .col(uuid(WorkspaceMember::WorkspaceId).not_null())
+                    .col(string_null(WorkspaceMember::UserId))
+                    .col(string(WorkspaceMember::Email).not_null())
+                    .col(string(WorkspaceMember::Role).not_null())
+                    .col(
+                        string(WorkspaceMember::Status)
+                            .default("pending")
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(WorkspaceMember::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(WorkspaceMember::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workspace_member_workspace")
+                            .from(WorkspaceMember::Table, WorkspaceMember::WorkspaceId)
+                            .to(Workspace::Table, Workspace::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workspace_member_user")
+                            .from(WorkspaceMember::Table, WorkspaceMember::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-workspace_member-workspace_id-email")
+                    .table(WorkspaceMember::Table)
+                    .col(WorkspaceMember::WorkspaceId)
+                    .col(WorkspaceMember::Email)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-workspace_member-user_id")
+                    .table(WorkspaceMember::Table)
+                    .col(WorkspaceMember::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_workspace_member_updated_at
+            BEFORE UPDATE ON "workspace_member"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WorkspaceMember::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Workspace {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum WorkspaceMember {
+    Table,
+    Id,
+    WorkspaceId,
+    UserId,
+    Email,
+    Role,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Each object has at most one position so layouts can be upserted.
+        // Keep the most recent row for objects that were saved twice.
+        db.execute_unprepared(
+            r#"
+            DELETE FROM "event_object_position" a
+            USING "event_object_position" b
+            WHERE a.event_object_id = b.event_object_id
+              AND (a.updated_at, a.ctid) < (b.updated_at, b.ctid);
+            "#,
+        )
+        .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-event_object_position-event_object_id")
+                    .table(EventObjectPosition::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-event_object_position-event_object_id")
+                    .table(EventObjectPosition::Table)
+                    .col(EventObjectPosition::EventObjectId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-event_object_position-event_object_id")
+                    .table(EventObjectPosition::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-event_object_position-event_object_id")
+                    .table(EventObjectPosition::Table)
+                    .col(EventObjectPosition::EventObjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EventObjectPosition {
+    Table,
+    EventObjectId,
+}
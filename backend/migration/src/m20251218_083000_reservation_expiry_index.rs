@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The hold sweeper repeatedly looks for pending reservations whose
+        // `expires_at` has passed.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reservation-status-expires_at")
+                    .table(Reservation::Table)
+                    .col(Reservation::Status)
+                    .col(Reservation::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-reservation-status-expires_at")
+                    .table(Reservation::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Status,
+    ExpiresAt,
+}
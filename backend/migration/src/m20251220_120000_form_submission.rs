@@ -0,0 +1,117 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FormSubmission::Table)
+                    .if_not_exists()
+                    .col(uuid(FormSubmission::Id).primary_key())
+                    .col(uuid(FormSubmission::FormId).not_null())
+                    .col(string(FormSubmission::UserId).not_null())
+                    .col(uuid_null(FormSubmission::ReservationId))
+                    .col(json_binary(FormSubmission::Data).not_null())
+                    .col(
+                        timestamp(FormSubmission::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(FormSubmission::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_form_submission_form")
+                            .from(FormSubmission::Table, FormSubmission::FormId)
+                            .to(Form::Table, Form::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_form_submission_user")
+                            .from(FormSubmission::Table, FormSubmission::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_form_submission_reservation")
+                            .from(FormSubmission::Table, FormSubmission::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-form_submission-form_id")
+                    .table(FormSubmission::Table)
+                    .col(FormSubmission::FormId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_form_submission_updated_at
+            BEFORE UPDATE ON "form_submission"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FormSubmission::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Form {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum FormSubmission {
+    Table,
+    Id,
+    FormId,
+    UserId,
+    ReservationId,
+    Data,
+    CreatedAt,
+    UpdatedAt,
+}
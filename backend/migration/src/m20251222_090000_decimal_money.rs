@@ -0,0 +1,124 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Money was stored as `double precision`, which cannot represent most
+        // prices exactly. Existing values are rounded to the smallest unit.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Section::Table)
+                    .modify_column(decimal_len(Section::Price, 12, 2).default(0).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reservation::Table)
+                    .modify_column(
+                        decimal_len(Reservation::TotalPrice, 12, 2)
+                            .default(0)
+                            .not_null(),
+                    )
+                    .add_column(string_len(Reservation::Currency, 3).default("THB").not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReservationItem::Table)
+                    .modify_column(
+                        decimal_len(ReservationItem::PriceAtBooking, 12, 2)
+                            .default(0)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .add_column(string_len(Event::Currency, 3).default("THB").not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .drop_column(Event::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReservationItem::Table)
+                    .modify_column(
+                        double(ReservationItem::PriceAtBooking)
+                            .default(0.0)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reservation::Table)
+                    .modify_column(double(Reservation::TotalPrice).default(0.0).not_null())
+                    .drop_column(Reservation::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Section::Table)
+                    .modify_column(double(Section::Price).default(0.0).not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum Section {
+    Table,
+    Price,
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    TotalPrice,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum ReservationItem {
+    Table,
+    PriceAtBooking,
+}
@@ -0,0 +1,152 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventSeries::Table)
+                    .if_not_exists()
+                    .col(uuid(EventSeries::Id).primary_key())
+                    .col(uuid(EventSeries::WorkspaceId).not_null())
+                    .col(uuid_null(EventSeries::TemplateEventId))
+                    .col(string(EventSeries::Title).not_null())
+                    .col(timestamp(EventSeries::StartsAt).not_null())
+                    .col(string(EventSeries::Frequency).not_null())
+                    .col(integer(EventSeries::Interval).default(1).not_null())
+                    .col(integer_null(EventSeries::Count))
+                    .col(timestamp_null(EventSeries::Until))
+                    .col(
+                        json_binary(EventSeries::Exclusions)
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(EventSeries::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(EventSeries::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_event_series_workspace")
+                            .from(EventSeries::Table, EventSeries::WorkspaceId)
+                            .to(Workspace::Table, Workspace::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_event_series_template_event")
+                            .from(EventSeries::Table, EventSeries::TemplateEventId)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .add_column(uuid_null(Event::SeriesId))
+                    .add_column(boolean(Event::SeriesDetached).default(false).not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_event_series")
+                            .from_tbl(Event::Table)
+                            .from_col(Event::SeriesId)
+                            .to_tbl(EventSeries::Table)
+                            .to_col(EventSeries::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-event-series_id-starts_at")
+                    .table(Event::Table)
+                    .col(Event::SeriesId)
+                    .col(Event::StartsAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_event_series_updated_at
+            BEFORE UPDATE ON "event_series"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .drop_foreign_key(Alias::new("fk_event_series"))
+                    .drop_column(Event::SeriesId)
+                    .drop_column(Event::SeriesDetached)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(EventSeries::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Workspace {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    StartsAt,
+    SeriesId,
+    SeriesDetached,
+}
+
+#[derive(DeriveIden)]
+enum EventSeries {
+    Table,
+    Id,
+    WorkspaceId,
+    TemplateEventId,
+    Title,
+    StartsAt,
+    Frequency,
+    Interval,
+    Count,
+    Until,
+    Exclusions,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,86 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table.clone())
+                        .add_column(timestamp_null(DeletedAt))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // The purge job scans for rows deleted before the retention cutoff.
+        for (name, table) in [
+            ("idx-workspace-deleted_at", Workspace::Table.into_iden()),
+            ("idx-event-deleted_at", Event::Table.into_iden()),
+            ("idx-section-deleted_at", Section::Table.into_iden()),
+            ("idx-form-deleted_at", Form::Table.into_iden()),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(name)
+                        .table(table)
+                        .col(DeletedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(DeletedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn tables() -> [DynIden; 4] {
+    [
+        Workspace::Table.into_iden(),
+        Event::Table.into_iden(),
+        Section::Table.into_iden(),
+        Form::Table.into_iden(),
+    ]
+}
+
+#[derive(DeriveIden)]
+struct DeletedAt;
+
+#[derive(DeriveIden)]
+enum Workspace {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Section {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Form {
+    Table,
+}
@@ -0,0 +1,93 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SectionSaleWindow::Table)
+                    .if_not_exists()
+                    .col(uuid(SectionSaleWindow::Id).primary_key())
+                    .col(uuid(SectionSaleWindow::SectionId).not_null())
+                    .col(string(SectionSaleWindow::Name).not_null())
+                    .col(decimal_len(SectionSaleWindow::Price, 12, 2).not_null())
+                    .col(timestamp_null(SectionSaleWindow::OpensAt))
+                    .col(timestamp_null(SectionSaleWindow::ClosesAt))
+                    .col(string_null(SectionSaleWindow::AccessCode))
+                    .col(
+                        timestamp(SectionSaleWindow::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(SectionSaleWindow::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_section_sale_window_section")
+                            .from(SectionSaleWindow::Table, SectionSaleWindow::SectionId)
+                            .to(Section::Table, Section::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-section_sale_window-section_id")
+                    .table(SectionSaleWindow::Table)
+                    .col(SectionSaleWindow::SectionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_section_sale_window_updated_at
+            BEFORE UPDATE ON "section_sale_window"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SectionSaleWindow::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Section {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum SectionSaleWindow {
+    Table,
+    Id,
+    SectionId,
+    Name,
+    Price,
+    OpensAt,
+    ClosesAt,
+    AccessCode,
+    CreatedAt,
+    UpdatedAt,
+}
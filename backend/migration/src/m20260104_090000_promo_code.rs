@@ -0,0 +1,219 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PromoCode::Table)
+                    .if_not_exists()
+                    .col(uuid(PromoCode::Id).primary_key())
+                    .col(uuid(PromoCode::WorkspaceId).not_null())
+                    .col(uuid_null(PromoCode::EventId))
+                    .col(uuid_null(PromoCode::SectionId))
+                    .col(string_len(PromoCode::Code, 64).not_null())
+                    .col(string_len(PromoCode::Kind, 16).not_null())
+                    .col(decimal_len(PromoCode::Amount, 12, 2).not_null())
+                    .col(integer_null(PromoCode::MaxUses))
+                    .col(integer_null(PromoCode::MaxUsesPerUser))
+                    .col(timestamp_null(PromoCode::ValidFrom))
+                    .col(timestamp_null(PromoCode::ValidUntil))
+                    .col(
+                        timestamp(PromoCode::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(PromoCode::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_promo_code_workspace")
+                            .from(PromoCode::Table, PromoCode::WorkspaceId)
+                            .to(Workspace::Table, Workspace::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_promo_code_event")
+                            .from(PromoCode::Table, PromoCode::EventId)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_promo_code_section")
+                            .from(PromoCode::Table, PromoCode::SectionId)
+                            .to(Section::Table, Section::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Codes are looked up by workspace at checkout, so they are unique
+        // there even when scoped to different events.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-promo_code-workspace_id-code")
+                    .table(PromoCode::Table)
+                    .col(PromoCode::WorkspaceId)
+                    .col(PromoCode::Code)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReservationDiscount::Table)
+                    .if_not_exists()
+                    .col(uuid(ReservationDiscount::Id).primary_key())
+                    .col(uuid(ReservationDiscount::ReservationId).not_null())
+                    .col(uuid_null(ReservationDiscount::PromoCodeId))
+                    .col(string_len(ReservationDiscount::Code, 64).not_null())
+                    .col(decimal_len(ReservationDiscount::Amount, 12, 2).not_null())
+                    .col(
+                        timestamp(ReservationDiscount::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(ReservationDiscount::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_reservation_discount_reservation")
+                            .from(ReservationDiscount::Table, ReservationDiscount::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    // The line keeps the code it was given under after the
+                    // promo code itself is deleted.
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_reservation_discount_promo_code")
+                            .from(ReservationDiscount::Table, ReservationDiscount::PromoCodeId)
+                            .to(PromoCode::Table, PromoCode::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reservation_discount-reservation_id")
+                    .table(ReservationDiscount::Table)
+                    .col(ReservationDiscount::ReservationId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reservation_discount-promo_code_id")
+                    .table(ReservationDiscount::Table)
+                    .col(ReservationDiscount::PromoCodeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in ["promo_code", "reservation_discount"] {
+            db.execute_unprepared(&format!(
+                r#"
+                CREATE TRIGGER update_{table}_updated_at
+                BEFORE UPDATE ON "{table}"
+                FOR EACH ROW
+                EXECUTE PROCEDURE update_updated_at_col();
+                "#
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReservationDiscount::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PromoCode::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Workspace {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Section {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PromoCode {
+    Table,
+    Id,
+    WorkspaceId,
+    EventId,
+    SectionId,
+    Code,
+    Kind,
+    Amount,
+    MaxUses,
+    MaxUsesPerUser,
+    ValidFrom,
+    ValidUntil,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ReservationDiscount {
+    Table,
+    Id,
+    ReservationId,
+    PromoCodeId,
+    Code,
+    Amount,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reservation::Table)
+                    .add_column(string_len_null(Reservation::PaymentProvider, 32))
+                    .add_column(string_null(Reservation::PaymentIntentId))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Provider callbacks find their reservation by intent id.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reservation-payment_provider-payment_intent_id")
+                    .table(Reservation::Table)
+                    .col(Reservation::PaymentProvider)
+                    .col(Reservation::PaymentIntentId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Reservation::Table)
+                    .drop_column(Reservation::PaymentProvider)
+                    .drop_column(Reservation::PaymentIntentId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    PaymentProvider,
+    PaymentIntentId,
+}
@@ -0,0 +1,179 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentEvent::Table)
+                    .if_not_exists()
+                    .col(uuid(PaymentEvent::Id).primary_key())
+                    .col(string_len(PaymentEvent::Provider, 32).not_null())
+                    .col(string(PaymentEvent::ProviderEventId).not_null())
+                    .col(string_len(PaymentEvent::EventType, 64).not_null())
+                    .col(json_binary(PaymentEvent::Payload).not_null())
+                    .col(uuid_null(PaymentEvent::ReservationId))
+                    .col(
+                        timestamp(PaymentEvent::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(PaymentEvent::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    // Events are kept as an audit trail after their
+                    // reservation is gone.
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_event_reservation")
+                            .from(PaymentEvent::Table, PaymentEvent::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Providers retry deliveries; a redelivered event hits this index and
+        // is not processed again.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-payment_event-provider-provider_event_id")
+                    .table(PaymentEvent::Table)
+                    .col(PaymentEvent::Provider)
+                    .col(PaymentEvent::ProviderEventId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-payment_event-reservation_id")
+                    .table(PaymentEvent::Table)
+                    .col(PaymentEvent::ReservationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_payment_event_updated_at
+            BEFORE UPDATE ON "payment_event"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentRefund::Table)
+                    .if_not_exists()
+                    .col(uuid(PaymentRefund::Id).primary_key())
+                    .col(uuid(PaymentRefund::ReservationId).not_null())
+                    .col(string_len(PaymentRefund::PaymentProvider, 32).not_null())
+                    .col(string(PaymentRefund::PaymentIntentId).not_null())
+                    .col(string(PaymentRefund::PaymentRefundId).not_null())
+                    .col(decimal_len(PaymentRefund::Amount, 12, 2).not_null())
+                    .col(
+                        timestamp(PaymentRefund::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(PaymentRefund::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_refund_reservation")
+                            .from(PaymentRefund::Table, PaymentRefund::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A payment that bought nothing is given back in full, and only once.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-payment_refund-payment_provider-payment_intent_id")
+                    .table(PaymentRefund::Table)
+                    .col(PaymentRefund::PaymentProvider)
+                    .col(PaymentRefund::PaymentIntentId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_payment_refund_updated_at
+            BEFORE UPDATE ON "payment_refund"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PaymentRefund::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PaymentEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PaymentEvent {
+    Table,
+    Id,
+    Provider,
+    ProviderEventId,
+    EventType,
+    Payload,
+    ReservationId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PaymentRefund {
+    Table,
+    Id,
+    ReservationId,
+    PaymentProvider,
+    PaymentIntentId,
+    PaymentRefundId,
+    Amount,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,137 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReservationItem::Table)
+                    .add_column(timestamp_null(ReservationItem::CancelledAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Refund::Table)
+                    .if_not_exists()
+                    .col(uuid(Refund::Id).primary_key())
+                    .col(uuid(Refund::ReservationId).not_null())
+                    .col(uuid(Refund::ReservationItemId).not_null())
+                    .col(decimal_len(Refund::Amount, 12, 2).not_null())
+                    .col(decimal_len(Refund::Fee, 12, 2).not_null())
+                    .col(decimal_len(Refund::Discount, 12, 2).not_null())
+                    .col(string_null(Refund::PaymentRefundId))
+                    .col(
+                        timestamp(Refund::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(Refund::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_refund_reservation")
+                            .from(Refund::Table, Refund::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_refund_reservation_item")
+                            .from(Refund::Table, Refund::ReservationItemId)
+                            .to(ReservationItem::Table, ReservationItem::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-refund-reservation_id")
+                    .table(Refund::Table)
+                    .col(Refund::ReservationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // A seat is cancelled, and so refunded, at most once.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-refund-reservation_item_id")
+                    .table(Refund::Table)
+                    .col(Refund::ReservationItemId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_refund_updated_at
+            BEFORE UPDATE ON "refund"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Refund::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ReservationItem::Table)
+                    .drop_column(ReservationItem::CancelledAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ReservationItem {
+    Table,
+    Id,
+    CancelledAt,
+}
+
+#[derive(DeriveIden)]
+enum Refund {
+    Table,
+    Id,
+    ReservationId,
+    ReservationItemId,
+    Amount,
+    Fee,
+    Discount,
+    PaymentRefundId,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ticket::Table)
+                    .if_not_exists()
+                    .col(uuid(Ticket::Id).primary_key())
+                    .col(uuid(Ticket::ReservationId).not_null())
+                    .col(uuid(Ticket::ReservationItemId).not_null())
+                    .col(uuid(Ticket::EventId).not_null())
+                    .col(string(Ticket::SeatLabel).not_null())
+                    .col(string(Ticket::Token).not_null())
+                    .col(timestamp_null(Ticket::RevokedAt))
+                    .col(
+                        timestamp(Ticket::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(Ticket::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ticket_reservation")
+                            .from(Ticket::Table, Ticket::ReservationId)
+                            .to(Reservation::Table, Reservation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ticket_reservation_item")
+                            .from(Ticket::Table, Ticket::ReservationItemId)
+                            .to(ReservationItem::Table, ReservationItem::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ticket_event")
+                            .from(Ticket::Table, Ticket::EventId)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One ticket per seat sold.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-ticket-reservation_item_id")
+                    .table(Ticket::Table)
+                    .col(Ticket::ReservationItemId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-ticket-reservation_id")
+                    .table(Ticket::Table)
+                    .col(Ticket::ReservationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-ticket-event_id")
+                    .table(Ticket::Table)
+                    .col(Ticket::EventId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_ticket_updated_at
+            BEFORE UPDATE ON "ticket"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Ticket::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reservation {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ReservationItem {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Ticket {
+    Table,
+    Id,
+    ReservationId,
+    ReservationItemId,
+    EventId,
+    SeatLabel,
+    Token,
+    RevokedAt,
+    CreatedAt,
+    UpdatedAt,
+}
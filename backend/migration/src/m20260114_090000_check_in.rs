@@ -0,0 +1,130 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CheckIn::Table)
+                    .if_not_exists()
+                    .col(uuid(CheckIn::Id).primary_key())
+                    .col(uuid(CheckIn::TicketId).not_null())
+                    .col(uuid(CheckIn::EventId).not_null())
+                    .col(string_len(CheckIn::Gate, 64).not_null())
+                    .col(string_len_null(CheckIn::Device, 128))
+                    .col(string(CheckIn::OperatorId).not_null())
+                    .col(
+                        timestamp(CheckIn::CheckedInAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(timestamp_null(CheckIn::UndoneAt))
+                    .col(string_null(CheckIn::UndoneBy))
+                    .col(
+                        timestamp(CheckIn::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp(CheckIn::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_check_in_ticket")
+                            .from(CheckIn::Table, CheckIn::TicketId)
+                            .to(Ticket::Table, Ticket::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_check_in_event")
+                            .from(CheckIn::Table, CheckIn::EventId)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one check-in per ticket is in effect; undone ones stay as
+        // history. Concurrent scans of the same ticket at two gates race on
+        // this index and only one of them wins.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-check_in-ticket_id-active")
+                    .table(CheckIn::Table)
+                    .col(CheckIn::TicketId)
+                    .unique()
+                    .and_where(Expr::col(CheckIn::UndoneAt).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-check_in-event_id")
+                    .table(CheckIn::Table)
+                    .col(CheckIn::EventId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER update_check_in_updated_at
+            BEFORE UPDATE ON "check_in"
+            FOR EACH ROW
+            EXECUTE PROCEDURE update_updated_at_col();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CheckIn::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Ticket {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum CheckIn {
+    Table,
+    Id,
+    TicketId,
+    EventId,
+    Gate,
+    Device,
+    OperatorId,
+    CheckedInAt,
+    UndoneAt,
+    UndoneBy,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,217 @@
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::model::{event, event_series, form, reservation, section, workspace, workspace_member};
+use crate::status::EventStatus;
+use crate::trash::SoftDelete;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Rows missing, in the trash or under a trashed parent are 404 before any permission check.
+
+/// Role of a user inside a workspace; `Owner` comes from `workspace.owner_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceRole {
+    Owner,
+    Admin,
+    Editor,
+    BoxOffice,
+    Viewer,
+}
+
+impl WorkspaceRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkspaceRole::Owner => "owner",
+            WorkspaceRole::Admin => "admin",
+            WorkspaceRole::Editor => "editor",
+            WorkspaceRole::BoxOffice => "box_office",
+            WorkspaceRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "owner" => Some(WorkspaceRole::Owner),
+            "admin" => Some(WorkspaceRole::Admin),
+            "editor" => Some(WorkspaceRole::Editor),
+            "box_office" => Some(WorkspaceRole::BoxOffice),
+            "viewer" => Some(WorkspaceRole::Viewer),
+            _ => None,
+        }
+    }
+
+    pub fn allows(&self, permission: Permission) -> bool {
+        use WorkspaceRole::*;
+        match permission {
+            Permission::View => true,
+            Permission::Edit => matches!(self, Owner | Admin | Editor),
+            Permission::Sell => matches!(self, Owner | Admin | BoxOffice),
+            Permission::Manage => matches!(self, Owner | Admin),
+            Permission::Own => matches!(self, Owner),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Read the workspace and everything inside it.
+    View,
+    /// Create, change and delete events, sections and forms.
+    Edit,
+    /// Hold and confirm reservations for buyers and check tickets in.
+    Sell,
+    /// Rename the workspace and manage its members.
+    Manage,
+    /// Delete the workspace.
+    Own,
+}
+
+pub const MEMBER_PENDING: &str = "pending";
+pub const MEMBER_ACTIVE: &str = "active";
+
+/// Caller's role in `workspace`, or `None` without access.
+pub async fn workspace_role(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    workspace: &workspace::Model,
+) -> Result<Option<WorkspaceRole>, AppError> {
+    if workspace.owner_id == user.id() {
+        return Ok(Some(WorkspaceRole::Owner));
+    }
+
+    let member = workspace_member::Entity::find()
+        .filter(workspace_member::Column::WorkspaceId.eq(workspace.id))
+        .filter(workspace_member::Column::UserId.eq(user.id()))
+        .filter(workspace_member::Column::Status.eq(MEMBER_ACTIVE))
+        .one(db)
+        .await?;
+
+    Ok(member.and_then(|member| WorkspaceRole::parse(&member.role)))
+}
+
+pub async fn check_workspace(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    workspace: &workspace::Model,
+    permission: Permission,
+) -> Result<WorkspaceRole, AppError> {
+    match workspace_role(db, user, workspace).await? {
+        Some(role) if role.allows(permission) => Ok(role),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
+pub async fn authorize_workspace(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    workspace_id: Uuid,
+    permission: Permission,
+) -> Result<workspace::Model, AppError> {
+    let workspace = workspace::Entity::find_live_by_id(workspace_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Workspace not found".to_string()))?;
+    check_workspace(db, user, &workspace, permission).await?;
+    Ok(workspace)
+}
+
+pub async fn authorize_event(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    event_id: Uuid,
+    permission: Permission,
+) -> Result<event::Model, AppError> {
+    let (event, workspace) = event::Entity::find_live_by_id(event_id)
+        .find_also_related(workspace::Entity)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Event not found".to_string()))?;
+    let workspace = live_workspace(workspace)?;
+    check_workspace(db, user, &workspace, permission).await?;
+    Ok(event)
+}
+
+/// Loads a public event, or one `user` can view in its workspace.
+pub async fn authorize_public_event(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    event_id: Uuid,
+) -> Result<event::Model, AppError> {
+    let event = event::Entity::find_live_by_id(event_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Event not found".to_string()))?;
+    if EventStatus::parse(&event.status).is_some_and(|status| status.is_public()) {
+        return Ok(event);
+    }
+    authorize_event(db, user, event_id, Permission::View).await
+}
+
+pub async fn authorize_section(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    section_id: Uuid,
+    permission: Permission,
+) -> Result<section::Model, AppError> {
+    let section = section::Entity::find_live_by_id(section_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Section not found".to_string()))?;
+    authorize_event(db, user, section.event_id, permission).await?;
+    Ok(section)
+}
+
+pub async fn authorize_form(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    form_id: Uuid,
+    permission: Permission,
+) -> Result<form::Model, AppError> {
+    let form = form::Entity::find_live_by_id(form_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Form not found".to_string()))?;
+    authorize_event(db, user, form.event_id, permission).await?;
+    Ok(form)
+}
+
+pub async fn authorize_series(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    series_id: Uuid,
+    permission: Permission,
+) -> Result<event_series::Model, AppError> {
+    let (series, workspace) = event_series::Entity::find_by_id(series_id)
+        .find_also_related(workspace::Entity)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Series not found".to_string()))?;
+    let workspace = live_workspace(workspace)?;
+    check_workspace(db, user, &workspace, permission).await?;
+    Ok(series)
+}
+
+fn live_workspace(workspace: Option<workspace::Model>) -> Result<workspace::Model, AppError> {
+    workspace
+        .filter(|workspace| workspace.deleted_at.is_none())
+        .ok_or(AppError::NotFound("Workspace not found".to_string()))
+}
+
+/// Buyers may act on their own reservations, staff need `Sell`.
+pub async fn authorize_reservation(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    reservation_id: Uuid,
+) -> Result<reservation::Model, AppError> {
+    let reservation = reservation::Entity::find_by_id(reservation_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::NotFound("Reservation not found".to_string()))?;
+    if reservation.user_id != user.id() {
+        authorize_event(db, user, reservation.event_id, Permission::Sell).await?;
+    }
+    Ok(reservation)
+}
@@ -1,11 +1,30 @@
+use crate::live::SeatFeed;
+use crate::payment::{MockPaymentProvider, PaymentProvider};
 use crate::routes::{
+    check_in::check_in_routes,
     event::event_routes,
+    event_object::event_object_routes,
+    event_status::event_status_routes,
     form::form_routes,
+    form_submission::form_submission_routes,
     health_check,
+    layout::layout_routes,
+    live::live_routes,
+    member::member_routes,
+    payment_webhook::payment_webhook_routes,
+    promo_code::promo_code_routes,
+    reservation::reservation_routes,
     section::section_routes,
+    series::series_routes,
+    ticket::ticket_routes,
+    trash::trash_routes,
     workspace::{workspace_routes, workspaces::workspaces_routes},
 };
+use crate::sweeper::RELEASED_SEATS_METRIC;
+use crate::ticket::TicketSigner;
+use crate::trash::PURGED_ROWS_METRIC;
 use axum::{Router, routing::get};
+use axum_prometheus::metrics::describe_counter;
 use axum_prometheus::{PrometheusMetricLayer, metrics_exporter_prometheus::PrometheusHandle};
 use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
 use eyre::Result;
@@ -15,17 +34,37 @@ use std::{
     sync::{Arc, OnceLock},
     time::Duration,
 };
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{Http, HttpAuthScheme, SecurityScheme},
+};
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub db: Arc<DatabaseConnection>,
+    pub live: SeatFeed,
+    pub payments: Arc<dyn PaymentProvider>,
+    pub tickets: TicketSigner,
 }
 
 impl AppState {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        AppState { db }
+        AppState {
+            db,
+            live: SeatFeed::default(),
+            payments: Arc::new(MockPaymentProvider::default()),
+            tickets: TicketSigner::development(),
+        }
+    }
+
+    pub fn with_payments(self, payments: Arc<dyn PaymentProvider>) -> Self {
+        AppState { payments, ..self }
+    }
+
+    pub fn with_tickets(self, tickets: TicketSigner) -> Self {
+        AppState { tickets, ..self }
     }
 }
 
@@ -46,27 +85,68 @@ pub async fn create_database() -> Result<DatabaseConnection> {
     Ok(db)
 }
 
+#[derive(OpenApi)]
+#[openapi(modifiers(&SessionSecurity), security(("session_token" = [])))]
+struct ApiDoc;
+
+struct SessionSecurity;
+
+impl Modify for SessionSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "session_token",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+    }
+}
+
 static PROMETHEUS: OnceLock<(PrometheusMetricLayer, PrometheusHandle)> = OnceLock::new();
 
-pub fn create_router(db: DatabaseConnection) -> Result<Router> {
-    let app_state = AppState::new(Arc::new(db));
-    let (prometheus_layer, metric_handle) = PROMETHEUS
-        .get_or_init(|| PrometheusMetricLayer::pair())
-        .clone();
+/// Runs before the jobs spawn, as metrics recorded earlier are dropped.
+fn describe_job_metrics() {
+    describe_counter!(
+        RELEASED_SEATS_METRIC,
+        "Seats released back to available after their reservation hold expired"
+    );
+    describe_counter!(
+        PURGED_ROWS_METRIC,
+        "Workspaces, events, sections and forms deleted for good after their retention period"
+    );
+}
+
+pub fn create_router(app_state: AppState) -> Result<Router> {
+    let (prometheus_layer, metric_handle) =
+        PROMETHEUS.get_or_init(PrometheusMetricLayer::pair).clone();
+    describe_job_metrics();
     // Build router and OpenAPI spec
-    let (router, api): (Router, utoipa::openapi::OpenApi) = OpenApiRouter::<AppState>::new()
-        .merge(workspace_routes())
-        .merge(workspaces_routes())
-        .merge(event_routes())
-        .merge(section_routes())
-        .merge(form_routes())
-        .route("/metrics", get(|| async move { metric_handle.render() }))
-        .route("/health", get(health_check))
-        .layer(prometheus_layer)
-        .layer(OtelInResponseLayer::default())
-        .layer(OtelAxumLayer::default())
-        .with_state(app_state.clone())
-        .split_for_parts();
+    let (router, api): (Router, utoipa::openapi::OpenApi) =
+        OpenApiRouter::<AppState>::with_openapi(ApiDoc::openapi())
+            .merge(workspace_routes())
+            .merge(workspaces_routes())
+            .merge(event_routes())
+            .merge(event_object_routes())
+            .merge(event_status_routes())
+            .merge(layout_routes())
+            .merge(live_routes())
+            .merge(section_routes())
+            .merge(series_routes())
+            .merge(trash_routes())
+            .merge(form_routes())
+            .merge(form_submission_routes())
+            .merge(member_routes())
+            .merge(promo_code_routes())
+            .merge(reservation_routes())
+            .merge(payment_webhook_routes())
+            .merge(ticket_routes())
+            .merge(check_in_routes())
+            .route("/metrics", get(|| async move { metric_handle.render() }))
+            .route("/health", get(health_check))
+            .layer(prometheus_layer)
+            .layer(OtelInResponseLayer)
+            .layer(OtelAxumLayer::default())
+            .with_state(app_state)
+            .split_for_parts();
 
     // Merge Swagger UI route
     let app = router.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api));
@@ -0,0 +1,79 @@
+use crate::app::AppState;
+use crate::error::AppError;
+use crate::model::{session, user};
+use axum::extract::FromRequestParts;
+use axum::http::{
+    HeaderMap,
+    header::{AUTHORIZATION, COOKIE},
+    request::Parts,
+};
+use chrono::Utc;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+const SESSION_COOKIES: [&str; 2] = [
+    "better-auth.session_token",
+    "__Secure-better-auth.session_token",
+];
+
+/// Caller resolved from a bearer token or the better-auth session cookie.
+#[derive(Clone, Debug)]
+pub struct AuthUser {
+    pub user: user::Model,
+    pub session: session::Model,
+}
+
+impl AuthUser {
+    pub fn id(&self) -> &str {
+        &self.user.id
+    }
+}
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        app_state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = session_token(&parts.headers).ok_or(AppError::Unauthorized)?;
+
+        let (session, user) = session::Entity::find()
+            .filter(session::Column::Token.eq(token))
+            .find_also_related(user::Entity)
+            .one(&*app_state.db)
+            .await?
+            .ok_or(AppError::Unauthorized)?;
+
+        if session.expires_at <= Utc::now().naive_utc() {
+            return Err(AppError::Unauthorized);
+        }
+        let user = user.ok_or(AppError::Unauthorized)?;
+
+        Ok(AuthUser { user, session })
+    }
+}
+
+fn session_token(headers: &HeaderMap) -> Option<String> {
+    if let Some(token) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        return Some(token.to_string());
+    }
+
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| SESSION_COOKIES.contains(name))
+        // better-auth signs the cookie as `<token>.<signature>`; only the
+        // token part is stored in the `session` table.
+        .and_then(|(_, value)| value.split('.').next())
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,305 @@
+use crate::dto::section::SeatCounts;
+use crate::error::AppError;
+use crate::model::{event, event_object, reservation, reservation_discount, reservation_item};
+use crate::status::{EventStatus, ReservationStatus, SeatStatus};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Expr, LockBehavior, LockType};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, Select, TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Holds every available seat of `object_ids` or fails with `Conflict`.
+pub async fn hold_seats(
+    db: &impl ConnectionTrait,
+    event_id: Uuid,
+    object_ids: &[Uuid],
+) -> Result<(), AppError> {
+    let result = event_object::Entity::update_many()
+        .col_expr(
+            event_object::Column::Status,
+            Expr::value(SeatStatus::Held.as_str()),
+        )
+        .filter(event_object::Column::EventId.eq(event_id))
+        .filter(event_object::Column::Id.is_in(object_ids.iter().copied()))
+        .filter(event_object::Column::Status.eq(SeatStatus::Available.as_str()))
+        .filter(event_object::Column::IsEnable.eq(true))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected != object_ids.len() as u64 {
+        return Err(AppError::Conflict(
+            "Some seats are no longer available".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Moves the seats in `object_ids` from `from` to `to`, returning how many changed.
+pub async fn transition_seats(
+    db: &impl ConnectionTrait,
+    object_ids: &[Uuid],
+    from: SeatStatus,
+    to: SeatStatus,
+) -> Result<u64, DbErr> {
+    if object_ids.is_empty() {
+        return Ok(0);
+    }
+    let result = event_object::Entity::update_many()
+        .col_expr(event_object::Column::Status, Expr::value(to.as_str()))
+        .filter(event_object::Column::Id.is_in(object_ids.iter().copied()))
+        .filter(event_object::Column::Status.eq(from.as_str()))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+fn unsold_seats(event_id: Uuid) -> Select<event_object::Entity> {
+    event_object::Entity::find()
+        .filter(event_object::Column::EventId.eq(event_id))
+        .filter(event_object::Column::IsEnable.eq(true))
+        .filter(event_object::Column::Status.ne(SeatStatus::Sold.as_str()))
+}
+
+pub async fn has_unsold_seats(db: &impl ConnectionTrait, event_id: Uuid) -> Result<bool, DbErr> {
+    Ok(unsold_seats(event_id).one(db).await?.is_some())
+}
+
+/// Flips an on-sale event to sold out once no seat is left unsold.
+pub async fn mark_sold_out(db: &impl ConnectionTrait, event_id: Uuid) -> Result<bool, DbErr> {
+    let unsold = unsold_seats(event_id)
+        .select_only()
+        .expr(Expr::value(1))
+        .into_query();
+    let result = event::Entity::update_many()
+        .col_expr(
+            event::Column::Status,
+            Expr::value(EventStatus::SoldOut.as_str()),
+        )
+        .filter(event::Column::Id.eq(event_id))
+        .filter(event::Column::Status.eq(EventStatus::OnSale.as_str()))
+        .filter(Expr::exists(unsold).not())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Reopens a sold-out event once a seat of it is back on sale.
+pub async fn reopen_sold_out(db: &impl ConnectionTrait, event_id: Uuid) -> Result<bool, DbErr> {
+    let result = event::Entity::update_many()
+        .col_expr(
+            event::Column::Status,
+            Expr::value(EventStatus::OnSale.as_str()),
+        )
+        .filter(event::Column::Id.eq(event_id))
+        .filter(event::Column::Status.eq(EventStatus::SoldOut.as_str()))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+#[derive(Debug, Default)]
+pub struct CancelledSales {
+    /// Pending reservations that were cancelled.
+    pub cancelled_holds: u64,
+    /// Confirmed reservations flagged for refund.
+    pub refunds_pending: u64,
+    /// Seats of the cancelled holds, released back to available.
+    pub released: Vec<Uuid>,
+}
+
+/// Cancels pending holds and flags confirmed reservations for refund.
+pub async fn cancel_event_sales(
+    db: &impl ConnectionTrait,
+    event_id: Uuid,
+) -> Result<CancelledSales, DbErr> {
+    let pending: Vec<Uuid> = reservation::Entity::find()
+        .filter(reservation::Column::EventId.eq(event_id))
+        .filter(reservation::Column::Status.eq(ReservationStatus::Pending.as_str()))
+        .lock_exclusive()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|reservation| reservation.id)
+        .collect();
+
+    let mut released = Vec::new();
+    if !pending.is_empty() {
+        released = reservation_item::Entity::find()
+            .filter(reservation_item::Column::ReservationId.is_in(pending.iter().copied()))
+            .filter(reservation_item::Column::CancelledAt.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|item| item.event_object_id)
+            .collect();
+        transition_seats(db, &released, SeatStatus::Held, SeatStatus::Available).await?;
+        reservation::Entity::update_many()
+            .col_expr(
+                reservation::Column::Status,
+                Expr::value(ReservationStatus::Cancelled.as_str()),
+            )
+            .col_expr(
+                reservation::Column::ExpiresAt,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .filter(reservation::Column::Id.is_in(pending.iter().copied()))
+            .exec(db)
+            .await?;
+    }
+
+    let refunds = reservation::Entity::update_many()
+        .col_expr(
+            reservation::Column::Status,
+            Expr::value(ReservationStatus::RefundPending.as_str()),
+        )
+        .filter(reservation::Column::EventId.eq(event_id))
+        .filter(reservation::Column::Status.eq(ReservationStatus::Confirmed.as_str()))
+        .exec(db)
+        .await?;
+
+    Ok(CancelledSales {
+        cancelled_holds: pending.len() as u64,
+        refunds_pending: refunds.rows_affected,
+        released,
+    })
+}
+
+pub async fn reservation_items(
+    db: &impl ConnectionTrait,
+    reservation_id: Uuid,
+) -> Result<Vec<reservation_item::Model>, DbErr> {
+    reservation_item::Entity::find()
+        .filter(reservation_item::Column::ReservationId.eq(reservation_id))
+        .all(db)
+        .await
+}
+
+pub fn booked_seat_ids(items: &[reservation_item::Model]) -> Vec<Uuid> {
+    items
+        .iter()
+        .filter(|item| item.cancelled_at.is_none())
+        .map(|item| item.event_object_id)
+        .collect()
+}
+
+pub async fn reservation_discount(
+    db: &impl ConnectionTrait,
+    reservation_id: Uuid,
+) -> Result<Option<reservation_discount::Model>, DbErr> {
+    reservation_discount::Entity::find()
+        .filter(reservation_discount::Column::ReservationId.eq(reservation_id))
+        .one(db)
+        .await
+}
+
+#[derive(FromQueryResult)]
+struct SeatStatusCount {
+    section_id: Option<Uuid>,
+    status: String,
+    seats: i64,
+}
+
+/// Enabled seats of every section in `event_id`, keyed by section id.
+pub async fn section_seat_counts(
+    db: &impl ConnectionTrait,
+    event_id: Uuid,
+) -> Result<HashMap<Uuid, SeatCounts>, DbErr> {
+    let rows = event_object::Entity::find()
+        .select_only()
+        .column(event_object::Column::SectionId)
+        .column(event_object::Column::Status)
+        .column_as(event_object::Column::Id.count(), "seats")
+        .filter(event_object::Column::EventId.eq(event_id))
+        .filter(event_object::Column::SectionId.is_not_null())
+        .filter(event_object::Column::IsEnable.eq(true))
+        .group_by(event_object::Column::SectionId)
+        .group_by(event_object::Column::Status)
+        .into_model::<SeatStatusCount>()
+        .all(db)
+        .await?;
+
+    let mut counts: HashMap<Uuid, SeatCounts> = HashMap::new();
+    for row in rows {
+        let Some(section_id) = row.section_id else {
+            continue;
+        };
+        let seats = row.seats as u64;
+        let count = counts.entry(section_id).or_default();
+        count.total += seats;
+        if row.status == SeatStatus::Available.as_str() {
+            count.available += seats;
+        } else if row.status == SeatStatus::Held.as_str() {
+            count.held += seats;
+        } else if row.status == SeatStatus::Sold.as_str() {
+            count.sold += seats;
+        }
+    }
+    Ok(counts)
+}
+
+#[derive(Debug, Default)]
+pub struct ExpiredHolds {
+    pub reservations: u64,
+    pub seats: u64,
+    /// Released `event_object` ids, grouped by event.
+    pub released: HashMap<Uuid, Vec<Uuid>>,
+}
+
+/// Expires up to `limit` lapsed holds, skipping rows locked elsewhere.
+pub async fn expire_holds(
+    db: &DatabaseConnection,
+    now: NaiveDateTime,
+    limit: u64,
+) -> Result<ExpiredHolds, DbErr> {
+    let txn = db.begin().await?;
+
+    let expired: HashMap<Uuid, Uuid> = reservation::Entity::find()
+        .filter(reservation::Column::Status.eq(ReservationStatus::Pending.as_str()))
+        .filter(reservation::Column::ExpiresAt.lte(now))
+        .order_by_asc(reservation::Column::ExpiresAt)
+        .limit(limit)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|reservation| (reservation.id, reservation.event_id))
+        .collect();
+    if expired.is_empty() {
+        return Ok(ExpiredHolds::default());
+    }
+
+    let items = reservation_item::Entity::find()
+        .filter(reservation_item::Column::ReservationId.is_in(expired.keys().copied()))
+        .filter(reservation_item::Column::CancelledAt.is_null())
+        .all(&txn)
+        .await?;
+    let seat_ids: Vec<Uuid> = items.iter().map(|item| item.event_object_id).collect();
+    let seats = transition_seats(&txn, &seat_ids, SeatStatus::Held, SeatStatus::Available).await?;
+
+    reservation::Entity::update_many()
+        .col_expr(
+            reservation::Column::Status,
+            Expr::value(ReservationStatus::Expired.as_str()),
+        )
+        .filter(reservation::Column::Id.is_in(expired.keys().copied()))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    let mut released: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for item in items {
+        released
+            .entry(expired[&item.reservation_id])
+            .or_default()
+            .push(item.event_object_id);
+    }
+    Ok(ExpiredHolds {
+        reservations: expired.len() as u64,
+        seats,
+        released,
+    })
+}
@@ -1,4 +1,16 @@
+pub mod check_in;
 pub mod event;
+pub mod event_object;
 pub mod form;
+pub mod form_submission;
+pub mod layout;
+pub mod live;
+pub mod member;
+pub mod payment;
+pub mod promo_code;
+pub mod reservation;
 pub mod section;
+pub mod series;
+pub mod ticket;
+pub mod trash;
 pub mod workspace;
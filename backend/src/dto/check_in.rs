@@ -0,0 +1,61 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::check_in;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct CheckInRequest {
+    /// Payload read from the ticket's QR code.
+    pub token: String,
+    /// Entrance the ticket was scanned at, e.g. `North`.
+    pub gate: String,
+    /// Scanner the ticket was read with.
+    pub device: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct CheckInResponse {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub gate: String,
+    pub device: Option<String>,
+    pub operator_id: String,
+    pub checked_in_at: NaiveDateTime,
+    /// Set once the check-in has been undone.
+    pub undone_at: Option<NaiveDateTime>,
+    pub undone_by: Option<String>,
+}
+
+impl From<check_in::Model> for CheckInResponse {
+    fn from(value: check_in::Model) -> Self {
+        Self {
+            id: value.id,
+            ticket_id: value.ticket_id,
+            gate: value.gate,
+            device: value.device,
+            operator_id: value.operator_id,
+            checked_in_at: value.checked_in_at,
+            undone_at: value.undone_at,
+            undone_by: value.undone_by,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct GateCheckIns {
+    pub gate: String,
+    pub checked_in: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct CheckInSummaryResponse {
+    /// Tickets of the event that have not been revoked.
+    pub tickets: u64,
+    pub checked_in: u64,
+    /// Tickets not checked in yet.
+    pub remaining: u64,
+    /// Check-ins in effect by the gate they were made at.
+    pub gates: Vec<GateCheckIns>,
+}
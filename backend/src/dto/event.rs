@@ -1,16 +1,26 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::dto::layout::LayoutObject;
+use crate::model::{form, section};
+use crate::pagination::SortOrder;
+use crate::timezone::{event_timezone, to_local};
+
 #[derive(Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct EventRequest {
     pub title: String,
     pub workspace_id: Uuid,
     pub description: Option<String>,
-    pub starts_at: Option<NaiveDateTime>,
-    pub ends_at: Option<NaiveDateTime>,
+    /// RFC 3339 time with an offset, e.g. `2025-01-03T19:30:00+07:00`.
+    pub starts_at: Option<DateTime<FixedOffset>>,
+    pub ends_at: Option<DateTime<FixedOffset>>,
+    /// IANA timezone the event takes place in. Defaults to `UTC`.
+    pub timezone: Option<String>,
+    /// ISO 4217 code all section prices are in. Defaults to `THB`.
+    pub currency: Option<String>,
     pub settings: Option<Value>,
 }
 
@@ -19,32 +29,135 @@ pub struct UpdateEventRequest {
     pub id: Uuid,
     pub title: Option<String>,
     pub description: Option<String>,
-    pub starts_at: Option<NaiveDateTime>,
-    pub ends_at: Option<NaiveDateTime>,
+    pub starts_at: Option<DateTime<FixedOffset>>,
+    pub ends_at: Option<DateTime<FixedOffset>>,
+    pub timezone: Option<String>,
+    pub currency: Option<String>,
     pub settings: Option<Value>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+/// Fields left out keep the source event's value.
+#[derive(Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct CloneEventRequest {
+    /// Workspace to create the copy in, the source's by default.
+    pub workspace_id: Option<Uuid>,
+    pub title: Option<String>,
+    pub starts_at: Option<DateTime<FixedOffset>>,
+    pub ends_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
 pub struct EventResponse {
     pub id: Uuid,
     pub title: String,
     pub workspace_id: Uuid,
+    /// `draft`, `published`, `on_sale`, `sold_out`, `closed` or `cancelled`.
+    pub status: String,
     pub description: Option<String>,
+    /// In UTC.
     pub starts_at: Option<NaiveDateTime>,
+    /// In UTC.
     pub ends_at: Option<NaiveDateTime>,
+    pub timezone: String,
+    /// `starts_at` in `timezone`, with its offset.
+    pub starts_at_local: Option<DateTime<FixedOffset>>,
+    /// `ends_at` in `timezone`, with its offset.
+    pub ends_at_local: Option<DateTime<FixedOffset>>,
+    pub currency: String,
     pub settings: Option<Value>,
+    /// Series this event is an occurrence of.
+    pub series_id: Option<Uuid>,
+    /// Set while the event is in the trash.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<crate::model::event::Model> for EventResponse {
     fn from(value: crate::model::event::Model) -> Self {
+        let tz = event_timezone(&value.timezone);
         Self {
             id: value.id,
             title: value.title,
             workspace_id: value.workspace_id,
+            status: value.status,
             description: value.description,
             starts_at: value.starts_at,
             ends_at: value.ends_at,
+            starts_at_local: value.starts_at.map(|starts_at| to_local(tz, starts_at)),
+            ends_at_local: value.ends_at.map(|ends_at| to_local(tz, ends_at)),
+            timezone: value.timezone,
+            currency: value.currency,
             settings: value.settings,
+            series_id: value.series_id,
+            deleted_at: value.deleted_at,
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSort {
+    /// Events without a start time come last in ascending order.
+    #[default]
+    StartsAt,
+    Title,
+    CreatedAt,
+}
+
+impl EventSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSort::StartsAt => "starts_at",
+            EventSort::Title => "title",
+            EventSort::CreatedAt => "created_at",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventListQuery {
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Page size, 20 by default and at most 100.
+    pub limit: Option<u64>,
+    /// Only events that are still running at or after this RFC 3339 time.
+    pub from: Option<DateTime<FixedOffset>>,
+    /// Only events that start at or before this RFC 3339 time.
+    pub to: Option<DateTime<FixedOffset>>,
+    /// Case-insensitive search in the title.
+    pub q: Option<String>,
+    pub sort: Option<EventSort>,
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct EventPage {
+    pub items: Vec<EventResponse>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct EventDetailSection {
+    pub section: section::Model,
+    pub objects: Vec<LayoutObject>,
+}
+
+/// An event with everything needed to render its page.
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct EventDetailResponse {
+    pub event: EventResponse,
+    pub sections: Vec<EventDetailSection>,
+    /// Objects outside any section, such as the stage or labels.
+    pub unsectioned_objects: Vec<LayoutObject>,
+    pub forms: Vec<form::Model>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct CancelEventResponse {
+    pub event: EventResponse,
+    /// Pending reservations cancelled, their seats released.
+    pub cancelled_holds: u64,
+    /// Confirmed reservations now waiting for a refund.
+    pub refunds_pending: u64,
+}
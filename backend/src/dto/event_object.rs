@@ -0,0 +1,101 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::event_object;
+
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct EventObjectRequest {
+    pub object_type: String,
+    pub section_id: Option<Uuid>,
+    pub label: Option<String>,
+    pub is_enable: Option<bool>,
+}
+
+/// Omitted fields keep their value; `null` clears `section_id` or `label`.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct UpdateEventObjectRequest {
+    pub object_type: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<Uuid>)]
+    pub section_id: Option<Option<Uuid>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub label: Option<Option<String>>,
+    pub is_enable: Option<bool>,
+}
+
+/// Items with an `id` update that object like [`UpdateEventObjectRequest`],
+/// the others are created.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BulkEventObjectItem {
+    pub id: Option<Uuid>,
+    pub object_type: String,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<Uuid>)]
+    pub section_id: Option<Option<Uuid>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub label: Option<Option<String>>,
+    pub is_enable: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BulkEventObjectRequest {
+    pub objects: Vec<BulkEventObjectItem>,
+}
+
+/// Tells a `null`, which is kept as `Some(None)`, from an omitted field.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct EventObjectResponse {
+    pub id: Uuid,
+    pub object_type: String,
+    pub event_id: Uuid,
+    pub section_id: Option<Uuid>,
+    pub label: Option<String>,
+    pub is_enable: bool,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<event_object::Model> for EventObjectResponse {
+    fn from(value: event_object::Model) -> Self {
+        Self {
+            id: value.id,
+            object_type: value.object_type,
+            event_id: value.event_id,
+            section_id: value.section_id,
+            label: value.label,
+            is_enable: value.is_enable,
+            status: value.status,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::form_submission;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FormSubmissionRequest {
+    /// Reservation these answers were collected for, if any.
+    pub reservation_id: Option<Uuid>,
+    /// Answers keyed by field name, validated against `form.schema`.
+    pub data: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct FormSubmissionResponse {
+    pub id: Uuid,
+    pub form_id: Uuid,
+    pub user_id: String,
+    pub reservation_id: Option<Uuid>,
+    pub data: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<form_submission::Model> for FormSubmissionResponse {
+    fn from(submission: form_submission::Model) -> Self {
+        Self {
+            id: submission.id,
+            form_id: submission.form_id,
+            user_id: submission.user_id,
+            reservation_id: submission.reservation_id,
+            data: submission.data,
+            created_at: submission.created_at,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{event_object, event_object_position, section};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct ObjectPosition {
+    pub position_x: f64,
+    pub position_y: f64,
+    pub rotation: f64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct LayoutObject {
+    pub id: Uuid,
+    pub object_type: String,
+    pub section_id: Option<Uuid>,
+    pub label: Option<String>,
+    pub is_enable: bool,
+    pub status: String,
+    pub position: Option<ObjectPosition>,
+}
+
+impl LayoutObject {
+    pub fn new(
+        object: event_object::Model,
+        position: Option<event_object_position::Model>,
+    ) -> Self {
+        Self {
+            id: object.id,
+            object_type: object.object_type,
+            section_id: object.section_id,
+            label: object.label,
+            is_enable: object.is_enable,
+            status: object.status,
+            position: position.map(|position| ObjectPosition {
+                position_x: position.position_x,
+                position_y: position.position_y,
+                rotation: position.rotation,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct LayoutResponse {
+    pub event_id: Uuid,
+    pub sections: Vec<section::Model>,
+    pub objects: Vec<LayoutObject>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct PositionRequest {
+    pub event_object_id: Uuid,
+    pub position_x: f64,
+    pub position_y: f64,
+    pub rotation: f64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct SaveLayoutRequest {
+    pub positions: Vec<PositionRequest>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct PositionResponse {
+    pub event_object_id: Uuid,
+    pub position_x: f64,
+    pub position_y: f64,
+    pub rotation: f64,
+}
+
+impl From<event_object_position::Model> for PositionResponse {
+    fn from(value: event_object_position::Model) -> Self {
+        Self {
+            event_object_id: value.event_object_id,
+            position_x: value.position_x,
+            position_y: value.position_y,
+            rotation: value.rotation,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Message pushed on `/event/{id}/live` whenever seats change status.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct SeatUpdate {
+    pub event_id: Uuid,
+    pub status: String,
+    pub event_object_ids: Vec<Uuid>,
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::access::WorkspaceRole;
+use crate::model::workspace_member;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct InviteMemberRequest {
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct MemberResponse {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub user_id: Option<String>,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<workspace_member::Model> for MemberResponse {
+    fn from(member: workspace_member::Model) -> Self {
+        Self {
+            id: member.id,
+            workspace_id: member.workspace_id,
+            user_id: member.user_id,
+            email: member.email,
+            role: member.role,
+            status: member.status,
+            created_at: member.created_at,
+            updated_at: member.updated_at,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct PaymentWebhookResponse {
+    /// The event had already been received and was not processed again.
+    pub duplicate: bool,
+    /// Status of the reservation the event is about once it was processed.
+    pub reservation_status: Option<String>,
+    /// The payment came after the reservation lapsed and was given back.
+    pub refunded: bool,
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::promo::DiscountKind;
+
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct PromoCodeRequest {
+    /// Matched case-insensitively at checkout and unique in the workspace.
+    pub code: String,
+    pub kind: DiscountKind,
+    /// Percent off for `percent` codes, an amount off for `fixed` ones.
+    pub amount: Decimal,
+    /// Limits the code to one event of the workspace.
+    pub event_id: Option<Uuid>,
+    /// Only discounts seats of this section; needs `event_id`.
+    pub section_id: Option<Uuid>,
+    /// Reservations that can use the code; unlimited when left out.
+    pub max_uses: Option<u32>,
+    /// Reservations each buyer can use the code for; unlimited when left out.
+    pub max_uses_per_user: Option<u32>,
+    pub valid_from: Option<DateTime<FixedOffset>>,
+    pub valid_until: Option<DateTime<FixedOffset>>,
+}
@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{refund, reservation, reservation_discount, reservation_item};
+
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ReservationRequest {
+    pub event_id: Uuid,
+    pub event_object_ids: Vec<Uuid>,
+    /// Unlocks sale windows behind an access code, such as a presale.
+    pub access_code: Option<String>,
+    pub promo_code: Option<String>,
+}
+
+/// Seats to cancel out of a reservation; the others stay booked.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CancelItemsRequest {
+    pub reservation_item_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct ReservationItemResponse {
+    pub id: Uuid,
+    pub event_object_id: Uuid,
+    pub price_at_booking: Decimal,
+    pub cancelled_at: Option<NaiveDateTime>,
+}
+
+impl From<reservation_item::Model> for ReservationItemResponse {
+    fn from(value: reservation_item::Model) -> Self {
+        Self {
+            id: value.id,
+            event_object_id: value.event_object_id,
+            price_at_booking: value.price_at_booking,
+            cancelled_at: value.cancelled_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct RefundResponse {
+    pub id: Uuid,
+    pub reservation_item_id: Uuid,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub discount: Decimal,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<refund::Model> for RefundResponse {
+    fn from(value: refund::Model) -> Self {
+        Self {
+            id: value.id,
+            reservation_item_id: value.reservation_item_id,
+            amount: value.amount,
+            fee: value.fee,
+            discount: value.discount,
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// Amount a promo code took off the reservation's seats.
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct ReservationDiscountResponse {
+    pub code: String,
+    pub amount: Decimal,
+}
+
+impl From<reservation_discount::Model> for ReservationDiscountResponse {
+    fn from(value: reservation_discount::Model) -> Self {
+        Self {
+            code: value.code,
+            amount: value.amount,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct ReservationResponse {
+    pub id: Uuid,
+    pub user_id: String,
+    pub event_id: Uuid,
+    pub status: String,
+    /// Price of the seats not cancelled, less any discount.
+    pub total_price: Decimal,
+    pub currency: String,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub items: Vec<ReservationItemResponse>,
+    pub discount: Option<ReservationDiscountResponse>,
+}
+
+impl ReservationResponse {
+    pub fn new(
+        reservation: reservation::Model,
+        items: Vec<reservation_item::Model>,
+        discount: Option<reservation_discount::Model>,
+    ) -> Self {
+        Self {
+            id: reservation.id,
+            user_id: reservation.user_id,
+            event_id: reservation.event_id,
+            status: reservation.status,
+            total_price: reservation.total_price,
+            currency: reservation.currency,
+            expires_at: reservation.expires_at,
+            created_at: reservation.created_at,
+            items: items
+                .into_iter()
+                .map(ReservationItemResponse::from)
+                .collect(),
+            discount: discount.map(ReservationDiscountResponse::from),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -8,14 +10,14 @@ use crate::model::section;
 pub struct SectionRequest {
     pub event_id: Uuid,
     pub title: String,
-    pub price: f64,
+    pub price: Decimal,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct UpdateSectionRequest {
     pub id: Uuid,
     pub title: Option<String>,
-    pub price: Option<f64>,
+    pub price: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, ToSchema, Debug)]
@@ -23,8 +25,37 @@ pub struct SectionResponse {
     // pub id: Uuid,
     // pub event_id: Uuid,
     // pub title: String,
-    // pub price: f64,
+    // pub price: Decimal,
     // pub created_at: NaiveDateTime,
     // pub updated_at: NaiveDateTime,
     pub section: section::Model,
 }
+
+/// Enabled seats of a section by status.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema, Debug, Default, Clone, Copy)]
+pub struct SeatCounts {
+    pub total: u64,
+    pub available: u64,
+    pub held: u64,
+    pub sold: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, ToSchema, Debug)]
+pub struct SectionSeatsResponse {
+    pub section: section::Model,
+    pub seats: SeatCounts,
+}
+
+/// One phase of a section's sales.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema, Debug, Clone)]
+pub struct SaleWindowRequest {
+    /// Shown to buyers, e.g. `Presale` or `General sale`.
+    pub name: String,
+    pub price: Decimal,
+    /// RFC 3339 time with an offset; open from the start when left out.
+    pub opens_at: Option<DateTime<FixedOffset>>,
+    /// Open until the event when left out.
+    pub closes_at: Option<DateTime<FixedOffset>>,
+    /// Buyers must give this code to book during the window.
+    pub access_code: Option<String>,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::dto::event::EventResponse;
+use crate::model::event_series;
+use crate::recurrence::Frequency;
+
+/// Creates one event per occurrence, each a copy of `template_event_id`.
+#[derive(Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SeriesRequest {
+    pub template_event_id: Uuid,
+    /// Defaults to the template's title.
+    pub title: Option<String>,
+    /// Start of the first occurrence, in the template's timezone.
+    pub starts_at: DateTime<FixedOffset>,
+    pub frequency: Frequency,
+    /// Repeat every `interval` days, weeks or months; 1 by default.
+    pub interval: Option<u32>,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<FixedOffset>>,
+    /// Local dates on which no occurrence is created.
+    pub exclusions: Option<Vec<NaiveDate>>,
+}
+
+/// Applied to every occurrence that has not been edited on its own.
+#[derive(Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct UpdateSeriesRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub settings: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct SeriesResponse {
+    pub series: event_series::Model,
+    pub occurrences: Vec<EventResponse>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OccurrenceQuery {
+    /// Only occurrences starting at or after this RFC 3339 time.
+    pub from: Option<DateTime<FixedOffset>>,
+    /// Only occurrences starting at or before this RFC 3339 time.
+    pub to: Option<DateTime<FixedOffset>>,
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::model::ticket;
+use crate::ticket::QrFormat;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct TicketResponse {
+    pub id: Uuid,
+    pub reservation_item_id: Uuid,
+    pub event_id: Uuid,
+    pub seat_label: String,
+    /// Signed payload shown as the ticket's QR code.
+    pub token: String,
+    /// Set once the seat has been cancelled.
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ticket::Model> for TicketResponse {
+    fn from(value: ticket::Model) -> Self {
+        Self {
+            id: value.id,
+            reservation_item_id: value.reservation_item_id,
+            event_id: value.event_id,
+            seat_label: value.seat_label,
+            token: value.token,
+            revoked_at: value.revoked_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QrQuery {
+    /// `svg` unless given.
+    pub format: Option<QrFormat>,
+}
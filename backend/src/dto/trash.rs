@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::dto::event::EventResponse;
+use crate::model::{form, section};
+
+/// Everything in a workspace's trash, most recently deleted first.
+#[derive(Serialize, Deserialize, PartialEq, Debug, ToSchema)]
+pub struct TrashResponse {
+    /// Days after `deleted_at` at which an item is purged for good.
+    pub retention_days: i64,
+    pub events: Vec<EventResponse>,
+    /// Sections deleted on their own.
+    pub sections: Vec<section::Model>,
+    /// Forms deleted on their own, as with `sections`.
+    pub forms: Vec<form::Model>,
+}
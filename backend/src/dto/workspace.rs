@@ -29,6 +29,8 @@ pub struct WorkspaceResponse {
     pub owner_id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Set while the workspace is in the trash.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<workspace::Model> for WorkspaceResponse {
@@ -39,6 +41,7 @@ impl From<workspace::Model> for WorkspaceResponse {
             name: workspace.name,
             created_at: workspace.created_at,
             updated_at: workspace.updated_at,
+            deleted_at: workspace.deleted_at,
         }
     }
 }
@@ -17,9 +17,18 @@ pub enum AppError {
     #[error("Unauthorized")]
     Unauthorized,
 
+    #[error("Forbidden")]
+    Forbidden,
+
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Payment required: {0}")]
+    PaymentRequired(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Validation error: {0}")]
     Validation(String),
 
@@ -48,7 +57,12 @@ impl IntoResponse for AppError {
             }
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "Not found", Some(msg)),
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized", None),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden", None),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "Bad request", Some(msg)),
+            AppError::PaymentRequired(msg) => {
+                (StatusCode::PAYMENT_REQUIRED, "Payment required", Some(msg))
+            }
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, "Conflict", Some(msg)),
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, "Validation error", Some(msg)),
             AppError::Internal => (
                 StatusCode::INTERNAL_SERVER_ERROR,
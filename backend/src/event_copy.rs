@@ -0,0 +1,223 @@
+//! Deep copies of an event: its sections, seat layout and forms.
+
+use crate::model::{
+    event, event_object, event_object_position, form, section, section_sale_window,
+};
+use crate::status::{EventStatus, SeatStatus};
+use chrono::{NaiveDateTime, TimeDelta};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, ModelTrait, QueryFilter,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Rows per multi-row `INSERT`, well below Postgres' limit on bind parameters.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct EventCopy {
+    pub workspace_id: Uuid,
+    pub title: String,
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+    pub series_id: Option<Uuid>,
+}
+
+/// An event loaded with everything hanging off it.
+pub struct EventTemplate {
+    pub event: event::Model,
+    sections: Vec<section::Model>,
+    windows: Vec<section_sale_window::Model>,
+    objects: Vec<event_object::Model>,
+    positions: Vec<event_object_position::Model>,
+    forms: Vec<form::Model>,
+}
+
+impl EventTemplate {
+    pub async fn load(db: &impl ConnectionTrait, event: event::Model) -> Result<Self, DbErr> {
+        let sections = event
+            .find_related(section::Entity)
+            .filter(section::Column::DeletedAt.is_null())
+            .all(db)
+            .await?;
+        let windows = section_sale_window::Entity::find()
+            .inner_join(section::Entity)
+            .filter(section::Column::EventId.eq(event.id))
+            .filter(section::Column::DeletedAt.is_null())
+            .all(db)
+            .await?;
+        let mut objects = event.find_related(event_object::Entity).all(db).await?;
+        let mut positions = event_object_position::Entity::find()
+            .inner_join(event_object::Entity)
+            .filter(event_object::Column::EventId.eq(event.id))
+            .all(db)
+            .await?;
+        let forms = event
+            .find_related(form::Entity)
+            .filter(form::Column::DeletedAt.is_null())
+            .all(db)
+            .await?;
+
+        // Objects of a deleted section stay behind with it.
+        objects.retain(|object| {
+            object
+                .section_id
+                .is_none_or(|section_id| sections.iter().any(|section| section.id == section_id))
+        });
+        let object_ids: HashSet<Uuid> = objects.iter().map(|object| object.id).collect();
+        positions.retain(|position| object_ids.contains(&position.event_object_id));
+        Ok(Self {
+            event,
+            sections,
+            windows,
+            objects,
+            positions,
+            forms,
+        })
+    }
+
+    /// Inserts one copy; run it inside a transaction.
+    pub async fn copy(
+        &self,
+        db: &impl ConnectionTrait,
+        copy: EventCopy,
+    ) -> Result<event::Model, DbErr> {
+        let source = &self.event;
+        let event = event::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            title: Set(copy.title),
+            workspace_id: Set(copy.workspace_id),
+            description: Set(source.description.clone()),
+            settings: Set(source.settings.clone()),
+            starts_at: Set(copy.starts_at),
+            ends_at: Set(copy.ends_at),
+            currency: Set(source.currency.clone()),
+            timezone: Set(source.timezone.clone()),
+            series_id: Set(copy.series_id),
+            status: Set(EventStatus::Draft.as_str().to_string()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        let section_ids: HashMap<Uuid, Uuid> = self
+            .sections
+            .iter()
+            .map(|section| (section.id, Uuid::new_v4()))
+            .collect();
+        let object_ids: HashMap<Uuid, Uuid> = self
+            .objects
+            .iter()
+            .map(|object| (object.id, Uuid::new_v4()))
+            .collect();
+
+        insert_batched(
+            db,
+            self.sections
+                .iter()
+                .map(|section| section::ActiveModel {
+                    id: Set(section_ids[&section.id]),
+                    event_id: Set(event.id),
+                    title: Set(section.title.clone()),
+                    price: Set(section.price),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+        .await?;
+        let shift = match (source.starts_at, copy.starts_at) {
+            (Some(from), Some(to)) => to - from,
+            _ => TimeDelta::zero(),
+        };
+        insert_batched(
+            db,
+            self.windows
+                .iter()
+                .filter_map(|window| {
+                    Some(section_sale_window::ActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        section_id: Set(*section_ids.get(&window.section_id)?),
+                        name: Set(window.name.clone()),
+                        price: Set(window.price),
+                        opens_at: Set(window.opens_at.map(|opens_at| opens_at + shift)),
+                        closes_at: Set(window.closes_at.map(|closes_at| closes_at + shift)),
+                        access_code: Set(window.access_code.clone()),
+                        ..Default::default()
+                    })
+                })
+                .collect(),
+        )
+        .await?;
+        insert_batched(
+            db,
+            self.objects
+                .iter()
+                .map(|object| event_object::ActiveModel {
+                    id: Set(object_ids[&object.id]),
+                    event_id: Set(event.id),
+                    object_type: Set(object.object_type.clone()),
+                    section_id: Set(object
+                        .section_id
+                        .and_then(|section_id| section_ids.get(&section_id).copied())),
+                    label: Set(object.label.clone()),
+                    is_enable: Set(object.is_enable),
+                    status: Set(SeatStatus::Available.as_str().to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+        .await?;
+        insert_batched(
+            db,
+            self.positions
+                .iter()
+                .filter_map(|position| {
+                    Some(event_object_position::ActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        event_object_id: Set(*object_ids.get(&position.event_object_id)?),
+                        position_x: Set(position.position_x),
+                        position_y: Set(position.position_y),
+                        rotation: Set(position.rotation),
+                        ..Default::default()
+                    })
+                })
+                .collect(),
+        )
+        .await?;
+        insert_batched(
+            db,
+            self.forms
+                .iter()
+                .map(|form| form::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    event_id: Set(event.id),
+                    schema: Set(form.schema.clone()),
+                    settings: Set(form.settings.clone()),
+                    title: Set(form.title.clone()),
+                    description: Set(form.description.clone()),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+        .await?;
+
+        Ok(event)
+    }
+}
+
+async fn insert_batched<A>(db: &impl ConnectionTrait, rows: Vec<A>) -> Result<(), DbErr>
+where
+    A: ActiveModelTrait + ActiveModelBehavior + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let mut rows = rows.into_iter().peekable();
+    while rows.peek().is_some() {
+        let batch: Vec<A> = rows.by_ref().take(INSERT_BATCH_SIZE).collect();
+        <A::Entity as EntityTrait>::insert_many(batch)
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
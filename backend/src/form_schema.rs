@@ -0,0 +1,226 @@
+//! Validation of form answers against the subset of JSON Schema the form builder emits.
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Path of the offending value, e.g. `guests[1].name`. Empty for the root.
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "`{}` {}", self.field, self.message)
+        }
+    }
+}
+
+pub fn validate(schema: &Value, data: &Value) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    validate_value(schema, data, "", &mut errors);
+    errors
+}
+
+pub fn check(schema: &Value, data: &Value) -> Result<(), AppError> {
+    let errors = validate(schema, data);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Validation(
+        errors
+            .iter()
+            .map(FieldError::to_string)
+            .collect::<Vec<_>>()
+            .join("; "),
+    ))
+}
+
+fn validate_value(schema: &Value, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let mut fail = |message: String| {
+        errors.push(FieldError {
+            field: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type")
+        && !matches_type(expected, value)
+    {
+        fail(format!("must be of type {}", describe_type(expected)));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        fail(format!(
+            "must be one of {}",
+            allowed
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    match value {
+        Value::String(text) => validate_string(schema, text, &mut fail),
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+                && number < minimum
+            {
+                fail(format!("must be at least {}", minimum));
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+                && number > maximum
+            {
+                fail(format!("must be at most {}", maximum));
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                && (items.len() as u64) < min
+            {
+                fail(format!("must have at least {} items", min));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                && (items.len() as u64) > max
+            {
+                fail(format!("must have at most {} items", max));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_value(item_schema, item, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+        Value::Object(fields) => validate_object(schema, fields, path, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn validate_string(schema: &Map<String, Value>, text: &str, fail: &mut impl FnMut(String)) {
+    let length = text.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+        && length < min
+    {
+        fail(format!("must be at least {} characters long", min));
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+        && length > max
+    {
+        fail(format!("must be at most {} characters long", max));
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match Regex::new(pattern) {
+            Ok(regex) if !regex.is_match(text) => {
+                fail(format!("must match the pattern `{}`", pattern))
+            }
+            Ok(_) => {}
+            Err(_) => fail("has an invalid pattern in the form schema".to_string()),
+        }
+    }
+    if schema.get("format").and_then(Value::as_str) == Some("email") && !looks_like_email(text) {
+        fail("must be an email address".to_string());
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    fields: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<FieldError>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let field_path = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+
+    for name in schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if fields.get(name).is_none_or(Value::is_null) {
+            errors.push(FieldError {
+                field: field_path(name),
+                message: "is required".to_string(),
+            });
+        }
+    }
+
+    let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+    for (name, value) in fields {
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(property) => validate_value(property, value, &field_path(name), errors),
+            None if closed => errors.push(FieldError {
+                field: field_path(name),
+                message: "is not a field of this form".to_string(),
+            }),
+            None => {}
+        }
+    }
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => matches_type_name(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| matches_type_name(name, value)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(name: &str, value: &Value) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        // JSON does not tell `3` from `3.0`.
+        "integer" => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("unknown").to_string(),
+    }
+}
+
+fn looks_like_email(text: &str) -> bool {
+    match text.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+        }
+        None => false,
+    }
+}
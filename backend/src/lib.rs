@@ -1,5 +1,23 @@
+pub mod access;
 pub mod app;
+pub mod auth;
+pub mod booking;
 pub mod dto;
-mod error;
+pub mod error;
+pub mod event_copy;
+pub mod form_schema;
+pub mod live;
 pub mod model;
+pub mod money;
+pub mod pagination;
+pub mod payment;
+pub mod promo;
+pub mod recurrence;
+pub mod refund;
 mod routes;
+pub mod sale_window;
+pub mod status;
+pub mod sweeper;
+pub mod ticket;
+pub mod timezone;
+pub mod trash;
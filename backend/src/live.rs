@@ -0,0 +1,100 @@
+//! In-process fan-out of seat status changes to `/event/{id}/live` sockets.
+
+use crate::dto::live::SeatUpdate;
+use crate::status::SeatStatus;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{
+    self,
+    error::{RecvError, TryRecvError},
+};
+use uuid::Uuid;
+
+/// Updates buffered per event before a slow subscriber starts lagging.
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Clone, Debug, Default)]
+pub struct SeatFeed {
+    channels: Arc<Mutex<HashMap<Uuid, broadcast::Sender<SeatUpdate>>>>,
+}
+
+impl SeatFeed {
+    pub fn subscribe(&self, event_id: Uuid) -> Subscription {
+        let mut channels = self.channels.lock().expect("seat feed lock poisoned");
+        let updates = channels
+            .entry(event_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe();
+        Subscription {
+            feed: self.clone(),
+            event_id,
+            updates: Some(updates),
+        }
+    }
+
+    /// Whether anyone is subscribed to `event_id`.
+    pub fn is_watched(&self, event_id: Uuid) -> bool {
+        let channels = self.channels.lock().expect("seat feed lock poisoned");
+        channels.contains_key(&event_id)
+    }
+
+    /// Tells everyone watching `event_id` that `event_object_ids` are now `status`.
+    pub fn publish(&self, event_id: Uuid, status: SeatStatus, event_object_ids: Vec<Uuid>) {
+        if event_object_ids.is_empty() {
+            return;
+        }
+
+        let mut channels = self.channels.lock().expect("seat feed lock poisoned");
+        let Some(sender) = channels.get(&event_id) else {
+            return;
+        };
+        let update = SeatUpdate {
+            event_id,
+            status: status.as_str().to_string(),
+            event_object_ids,
+        };
+        if sender.send(update).is_err() {
+            channels.remove(&event_id);
+        }
+    }
+}
+
+/// Updates of one event for one subscriber. The event's channel goes away
+/// with its last subscription, so events nobody watches are not kept around.
+#[derive(Debug)]
+pub struct Subscription {
+    feed: SeatFeed,
+    event_id: Uuid,
+    updates: Option<broadcast::Receiver<SeatUpdate>>,
+}
+
+impl Subscription {
+    pub async fn recv(&mut self) -> Result<SeatUpdate, RecvError> {
+        self.receiver().recv().await
+    }
+
+    pub fn try_recv(&mut self) -> Result<SeatUpdate, TryRecvError> {
+        self.receiver().try_recv()
+    }
+
+    fn receiver(&mut self) -> &mut broadcast::Receiver<SeatUpdate> {
+        self.updates
+            .as_mut()
+            .expect("receiver is only taken when dropped")
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut channels = self.feed.channels.lock().expect("seat feed lock poisoned");
+        // Dropped under the lock so a concurrent subscribe either comes first
+        // and keeps the channel, or finds it gone and opens a new one.
+        drop(self.updates.take());
+        if channels
+            .get(&self.event_id)
+            .is_some_and(|sender| sender.receiver_count() == 0)
+        {
+            channels.remove(&self.event_id);
+        }
+    }
+}
@@ -1,8 +1,13 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use crate::app::create_router;
+use crate::app::{AppState, create_router};
 use crate::observe::create_logging_provider;
 use crate::observe::create_oltp_provider;
+use crate::payment::create_payment_provider;
+use crate::sweeper::spawn_reservation_sweeper;
+use crate::ticket::create_ticket_signer;
+use crate::trash::spawn_trash_purger;
 use backend::app::create_database;
 use eyre::Result;
 use opentelemetry::global;
@@ -12,13 +17,31 @@ use tracing::info;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
+pub mod access;
 pub mod app;
+pub mod auth;
+pub mod booking;
 pub mod dto;
 pub mod error;
+pub mod event_copy;
+pub mod form_schema;
+pub mod live;
 pub mod model;
+pub mod money;
 mod observe;
+pub mod pagination;
+pub mod payment;
 pub mod prometheus;
+pub mod promo;
+pub mod recurrence;
+pub mod refund;
 pub mod routes;
+pub mod sale_window;
+pub mod status;
+pub mod sweeper;
+pub mod ticket;
+pub mod timezone;
+pub mod trash;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -38,7 +61,13 @@ async fn main() -> Result<()> {
 
     let db = create_database().await?;
 
-    let app = create_router(db)?;
+    let app_state = AppState::new(Arc::new(db))
+        .with_payments(create_payment_provider()?)
+        .with_tickets(create_ticket_signer()?);
+
+    let app = create_router(app_state.clone())?;
+    spawn_trash_purger(app_state.clone());
+    spawn_reservation_sweeper(app_state);
     let port: u16 = std::env::var("PORT")
         .ok()
         .and_then(|v| v.parse().ok())
@@ -17,20 +17,43 @@ pub struct Model {
     pub settings: Option<Json>,
     pub starts_at: Option<DateTime>,
     pub ends_at: Option<DateTime>,
+    pub currency: String,
+    /// IANA timezone the event takes place in; times are stored in UTC.
+    pub timezone: String,
+    pub series_id: Option<Uuid>,
+    /// Set once the occurrence is edited on its own.
+    pub series_detached: bool,
+    /// One of `status::EventStatus`; only `on_sale` events take reservations.
+    pub status: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::check_in::Entity")]
+    CheckIn,
     #[sea_orm(has_many = "super::event_object::Entity")]
     EventObject,
+    #[sea_orm(
+        belongs_to = "super::event_series::Entity",
+        from = "Column::SeriesId",
+        to = "super::event_series::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    EventSeries,
     #[sea_orm(has_many = "super::form::Entity")]
     Form,
+    #[sea_orm(has_many = "super::promo_code::Entity")]
+    PromoCode,
     #[sea_orm(has_many = "super::reservation::Entity")]
     Reservation,
     #[sea_orm(has_many = "super::section::Entity")]
     Section,
+    #[sea_orm(has_many = "super::ticket::Entity")]
+    Ticket,
     #[sea_orm(
         belongs_to = "super::workspace::Entity",
         from = "Column::WorkspaceId",
@@ -41,18 +64,36 @@ pub enum Relation {
     Workspace,
 }
 
+impl Related<super::check_in::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CheckIn.def()
+    }
+}
+
 impl Related<super::event_object::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::EventObject.def()
     }
 }
 
+impl Related<super::event_series::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventSeries.def()
+    }
+}
+
 impl Related<super::form::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Form.def()
     }
 }
 
+impl Related<super::promo_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PromoCode.def()
+    }
+}
+
 impl Related<super::reservation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Reservation.def()
@@ -65,6 +106,12 @@ impl Related<super::section::Entity> for Entity {
     }
 }
 
+impl Related<super::ticket::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ticket.def()
+    }
+}
+
 impl Related<super::workspace::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Workspace.def()
@@ -18,6 +18,7 @@ pub struct Model {
     pub description: Option<String>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -30,6 +31,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Event,
+    #[sea_orm(has_many = "super::form_submission::Entity")]
+    FormSubmission,
 }
 
 impl Related<super::event::Entity> for Entity {
@@ -38,4 +41,10 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+impl Related<super::form_submission::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FormSubmission.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -3,14 +3,25 @@
 pub mod prelude;
 
 pub mod account;
+pub mod check_in;
 pub mod event;
 pub mod event_object;
 pub mod event_object_position;
+pub mod event_series;
 pub mod form;
+pub mod form_submission;
+pub mod payment_event;
+pub mod payment_refund;
+pub mod promo_code;
+pub mod refund;
 pub mod reservation;
+pub mod reservation_discount;
 pub mod reservation_item;
 pub mod section;
+pub mod section_sale_window;
 pub mod session;
+pub mod ticket;
 pub mod user;
 pub mod verification;
 pub mod workspace;
+pub mod workspace_member;
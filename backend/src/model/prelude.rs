@@ -1,14 +1,25 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
 
 pub use super::account::Entity as Account;
+pub use super::check_in::Entity as CheckIn;
 pub use super::event::Entity as Event;
 pub use super::event_object::Entity as EventObject;
 pub use super::event_object_position::Entity as EventObjectPosition;
+pub use super::event_series::Entity as EventSeries;
 pub use super::form::Entity as Form;
+pub use super::form_submission::Entity as FormSubmission;
+pub use super::payment_event::Entity as PaymentEvent;
+pub use super::payment_refund::Entity as PaymentRefund;
+pub use super::promo_code::Entity as PromoCode;
+pub use super::refund::Entity as Refund;
 pub use super::reservation::Entity as Reservation;
+pub use super::reservation_discount::Entity as ReservationDiscount;
 pub use super::reservation_item::Entity as ReservationItem;
 pub use super::section::Entity as Section;
+pub use super::section_sale_window::Entity as SectionSaleWindow;
 pub use super::session::Entity as Session;
+pub use super::ticket::Entity as Ticket;
 pub use super::user::Entity as User;
 pub use super::verification::Entity as Verification;
 pub use super::workspace::Entity as Workspace;
+pub use super::workspace_member::Entity as WorkspaceMember;
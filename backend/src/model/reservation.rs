@@ -4,7 +4,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, utoipa :: ToSchema,
+    Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, utoipa :: ToSchema,
 )]
 #[sea_orm(table_name = "reservation")]
 pub struct Model {
@@ -13,9 +13,13 @@ pub struct Model {
     pub user_id: String,
     pub event_id: Uuid,
     pub status: String,
-    #[sea_orm(column_type = "Double")]
-    pub total_price: f64,
+    #[sea_orm(column_type = "Decimal(Some((12, 2)))")]
+    pub total_price: Decimal,
+    pub currency: String,
     pub expires_at: Option<DateTime>,
+    /// Set once the reservation is paid.
+    pub payment_provider: Option<String>,
+    pub payment_intent_id: Option<String>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -30,8 +34,20 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Event,
+    #[sea_orm(has_many = "super::form_submission::Entity")]
+    FormSubmission,
+    #[sea_orm(has_many = "super::payment_event::Entity")]
+    PaymentEvent,
+    #[sea_orm(has_many = "super::payment_refund::Entity")]
+    PaymentRefund,
+    #[sea_orm(has_many = "super::refund::Entity")]
+    Refund,
+    #[sea_orm(has_many = "super::reservation_discount::Entity")]
+    ReservationDiscount,
     #[sea_orm(has_many = "super::reservation_item::Entity")]
     ReservationItem,
+    #[sea_orm(has_many = "super::ticket::Entity")]
+    Ticket,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::UserId",
@@ -48,12 +64,48 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+impl Related<super::form_submission::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FormSubmission.def()
+    }
+}
+
+impl Related<super::payment_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentEvent.def()
+    }
+}
+
+impl Related<super::payment_refund::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentRefund.def()
+    }
+}
+
+impl Related<super::refund::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Refund.def()
+    }
+}
+
+impl Related<super::reservation_discount::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReservationDiscount.def()
+    }
+}
+
 impl Related<super::reservation_item::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ReservationItem.def()
     }
 }
 
+impl Related<super::ticket::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ticket.def()
+    }
+}
+
 impl Related<super::user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::User.def()
@@ -4,7 +4,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, utoipa :: ToSchema,
+    Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, utoipa :: ToSchema,
 )]
 #[sea_orm(table_name = "reservation_item")]
 pub struct Model {
@@ -12,8 +12,10 @@ pub struct Model {
     pub id: Uuid,
     pub reservation_id: Uuid,
     pub event_object_id: Uuid,
-    #[sea_orm(column_type = "Double")]
-    pub price_at_booking: f64,
+    #[sea_orm(column_type = "Decimal(Some((12, 2)))")]
+    pub price_at_booking: Decimal,
+    /// Set when the seat was cancelled and handed back.
+    pub cancelled_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -28,6 +30,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     EventObject,
+    #[sea_orm(has_many = "super::refund::Entity")]
+    Refund,
     #[sea_orm(
         belongs_to = "super::reservation::Entity",
         from = "Column::ReservationId",
@@ -36,6 +40,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Reservation,
+    #[sea_orm(has_many = "super::ticket::Entity")]
+    Ticket,
 }
 
 impl Related<super::event_object::Entity> for Entity {
@@ -44,10 +50,22 @@ impl Related<super::event_object::Entity> for Entity {
     }
 }
 
+impl Related<super::refund::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Refund.def()
+    }
+}
+
 impl Related<super::reservation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Reservation.def()
     }
 }
 
+impl Related<super::ticket::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ticket.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -4,7 +4,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, utoipa :: ToSchema,
+    Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, utoipa :: ToSchema,
 )]
 #[sea_orm(table_name = "section")]
 pub struct Model {
@@ -12,10 +12,11 @@ pub struct Model {
     pub id: Uuid,
     pub event_id: Uuid,
     pub title: String,
-    #[sea_orm(column_type = "Double")]
-    pub price: f64,
+    #[sea_orm(column_type = "Decimal(Some((12, 2)))")]
+    pub price: Decimal,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -30,6 +31,10 @@ pub enum Relation {
     Event,
     #[sea_orm(has_many = "super::event_object::Entity")]
     EventObject,
+    #[sea_orm(has_many = "super::promo_code::Entity")]
+    PromoCode,
+    #[sea_orm(has_many = "super::section_sale_window::Entity")]
+    SectionSaleWindow,
 }
 
 impl Related<super::event::Entity> for Entity {
@@ -44,4 +49,16 @@ impl Related<super::event_object::Entity> for Entity {
     }
 }
 
+impl Related<super::promo_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PromoCode.def()
+    }
+}
+
+impl Related<super::section_sale_window::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SectionSaleWindow.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -23,12 +23,16 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::account::Entity")]
     Account,
+    #[sea_orm(has_many = "super::form_submission::Entity")]
+    FormSubmission,
     #[sea_orm(has_many = "super::reservation::Entity")]
     Reservation,
     #[sea_orm(has_many = "super::session::Entity")]
     Session,
     #[sea_orm(has_many = "super::workspace::Entity")]
     Workspace,
+    #[sea_orm(has_many = "super::workspace_member::Entity")]
+    WorkspaceMember,
 }
 
 impl Related<super::account::Entity> for Entity {
@@ -37,6 +41,12 @@ impl Related<super::account::Entity> for Entity {
     }
 }
 
+impl Related<super::form_submission::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FormSubmission.def()
+    }
+}
+
 impl Related<super::reservation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Reservation.def()
@@ -55,4 +65,10 @@ impl Related<super::workspace::Entity> for Entity {
     }
 }
 
+impl Related<super::workspace_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WorkspaceMember.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
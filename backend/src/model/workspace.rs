@@ -14,12 +14,17 @@ pub struct Model {
     pub owner_id: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::event::Entity")]
     Event,
+    #[sea_orm(has_many = "super::event_series::Entity")]
+    EventSeries,
+    #[sea_orm(has_many = "super::promo_code::Entity")]
+    PromoCode,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::OwnerId",
@@ -28,6 +33,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     User,
+    #[sea_orm(has_many = "super::workspace_member::Entity")]
+    WorkspaceMember,
 }
 
 impl Related<super::event::Entity> for Entity {
@@ -36,10 +43,28 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+impl Related<super::event_series::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventSeries.def()
+    }
+}
+
+impl Related<super::promo_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PromoCode.def()
+    }
+}
+
 impl Related<super::user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::User.def()
     }
 }
 
+impl Related<super::workspace_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WorkspaceMember.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
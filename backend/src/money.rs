@@ -0,0 +1,35 @@
+use crate::error::AppError;
+use sea_orm::prelude::Decimal;
+
+/// Fractional digits stored by the `NUMERIC(12, 2)` money columns.
+pub const PRICE_SCALE: u32 = 2;
+
+pub const DEFAULT_CURRENCY: &str = "THB";
+
+/// Rejects negative prices and prices more precise than can be stored.
+pub fn validate_price(price: Decimal) -> Result<Decimal, AppError> {
+    if price.is_sign_negative() {
+        return Err(AppError::Validation(
+            "`price` must not be negative".to_string(),
+        ));
+    }
+    let price = price.normalize();
+    if price.scale() > PRICE_SCALE {
+        return Err(AppError::Validation(format!(
+            "`price` must have at most {} decimal places",
+            PRICE_SCALE
+        )));
+    }
+    Ok(price)
+}
+
+/// Normalises an ISO 4217 currency code such as `thb` to `THB`.
+pub fn validate_currency(code: &str) -> Result<String, AppError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.bytes().all(|byte| byte.is_ascii_uppercase()) {
+        return Err(AppError::Validation(
+            "`currency` must be a three-letter ISO 4217 code".to_string(),
+        ));
+    }
+    Ok(code)
+}
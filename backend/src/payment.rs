@@ -0,0 +1,161 @@
+//! Payment providers charge buyers when a reservation is confirmed.
+
+use crate::error::AppError;
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use hmac::{Hmac, Mac};
+use sea_orm::prelude::Decimal;
+use sha2::Sha256;
+use std::env;
+use std::fmt::Debug;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+mod mock;
+
+pub use mock::MockPaymentProvider;
+
+/// Header webhooks carry the provider's signature of their body in.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "payment-signature";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentIntentStatus {
+    /// Created, the buyer has not been charged yet.
+    RequiresCapture,
+    Captured,
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentIntent {
+    pub id: String,
+    pub reservation_id: Uuid,
+    pub amount: Decimal,
+    pub currency: String,
+    pub status: PaymentIntentStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentRefund {
+    pub id: String,
+    pub intent_id: String,
+    pub amount: Decimal,
+}
+
+/// What a webhook says happened to a payment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentOutcome {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookEvent {
+    /// Id of the event at the provider; redeliveries of it keep the same id.
+    pub id: String,
+    pub event_type: String,
+    /// `None` for events that do not settle a payment.
+    pub outcome: Option<PaymentOutcome>,
+    pub intent_id: Option<String>,
+    pub reservation_id: Option<Uuid>,
+    /// Amount the event reports as paid, when it reports one.
+    pub amount: Option<Decimal>,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Error, Debug)]
+pub enum PaymentError {
+    #[error("Payment declined: {0}")]
+    Declined(String),
+
+    #[error("Unknown payment intent `{0}`")]
+    UnknownIntent(String),
+
+    #[error("Payment intent `{0}` has already been refunded")]
+    AlreadyRefunded(String),
+
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Payment provider error: {0}")]
+    Provider(String),
+}
+
+impl From<PaymentError> for AppError {
+    fn from(err: PaymentError) -> Self {
+        match err {
+            PaymentError::Declined(reason) => AppError::PaymentRequired(reason),
+            PaymentError::UnknownIntent(id) => {
+                AppError::NotFound(format!("Payment intent `{}` not found", id))
+            }
+            PaymentError::AlreadyRefunded(id) => {
+                AppError::Conflict(format!("Payment intent `{}` has already been refunded", id))
+            }
+            PaymentError::InvalidSignature => AppError::Unauthorized,
+            PaymentError::InvalidPayload(err) => AppError::BadRequest(err),
+            PaymentError::Provider(err) => {
+                tracing::error!("Payment provider error: {}", err);
+                AppError::Internal
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync + Debug {
+    /// Stored on reservations and used in webhook URLs, e.g. `mock`.
+    fn name(&self) -> &'static str;
+
+    /// Creates an intent for `reservation_id`, or returns the one already created.
+    async fn create_intent(
+        &self,
+        reservation_id: Uuid,
+        amount: Decimal,
+        currency: &str,
+    ) -> Result<PaymentIntent, PaymentError>;
+
+    /// Charges the buyer, failing with [`PaymentError::Declined`] when declined.
+    async fn capture(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError>;
+
+    /// Gives `amount` of a captured intent back to the buyer.
+    async fn refund(&self, intent_id: &str, amount: Decimal)
+    -> Result<PaymentRefund, PaymentError>;
+
+    /// Checks that a webhook `payload` was sent by the provider.
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<(), PaymentError>;
+
+    /// Reads a webhook `payload` that passed [`Self::verify_webhook`].
+    fn parse_webhook(&self, payload: &[u8]) -> Result<WebhookEvent, PaymentError>;
+}
+
+pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Checks a hex encoded HMAC-SHA256 `signature` of `payload` in constant time.
+pub fn verify_payload(secret: &[u8], payload: &[u8], signature: &str) -> Result<(), PaymentError> {
+    let signature = hex::decode(signature.trim()).map_err(|_| PaymentError::InvalidSignature)?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload);
+    mac.verify_slice(&signature)
+        .map_err(|_| PaymentError::InvalidSignature)
+}
+
+/// Provider selected by `PAYMENT_PROVIDER`, the mock one by default.
+pub fn create_payment_provider() -> Result<Arc<dyn PaymentProvider>> {
+    let provider = env::var("PAYMENT_PROVIDER").unwrap_or_else(|_| "mock".to_string());
+    match provider.as_str() {
+        "mock" => {
+            let secret = env::var("MOCK_PAYMENT_WEBHOOK_SECRET")
+                .map_err(|_| eyre!("MOCK_PAYMENT_WEBHOOK_SECRET must be set"))?;
+            Ok(Arc::new(MockPaymentProvider::new(secret)))
+        }
+        other => Err(eyre!("Unknown payment provider `{}`", other)),
+    }
+}
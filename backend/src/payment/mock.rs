@@ -0,0 +1,218 @@
+//! In-process payment provider that keeps its intents in memory.
+
+use super::{
+    PaymentError, PaymentIntent, PaymentIntentStatus, PaymentOutcome, PaymentProvider,
+    PaymentRefund, WebhookEvent, sign_payload, verify_payload,
+};
+use async_trait::async_trait;
+use sea_orm::prelude::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Webhook secret of the default provider tests use.
+const DEFAULT_WEBHOOK_SECRET: &str = "mock_webhook_secret";
+
+#[derive(Deserialize)]
+struct MockWebhook {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    data: MockWebhookData,
+}
+
+#[derive(Deserialize, Default)]
+struct MockWebhookData {
+    intent_id: Option<String>,
+    reservation_id: Option<Uuid>,
+    amount: Option<Decimal>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    intents: HashMap<String, PaymentIntent>,
+    refunds: Vec<PaymentRefund>,
+    decline_reason: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockPaymentProvider {
+    webhook_secret: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Default for MockPaymentProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_WEBHOOK_SECRET.to_string())
+    }
+}
+
+impl MockPaymentProvider {
+    pub fn new(webhook_secret: String) -> Self {
+        Self {
+            webhook_secret,
+            state: Arc::default(),
+        }
+    }
+
+    /// Makes every capture fail with `reason` until [`Self::accept`] is called.
+    pub fn decline(&self, reason: &str) {
+        self.state().decline_reason = Some(reason.to_string());
+    }
+
+    pub fn accept(&self) {
+        self.state().decline_reason = None;
+    }
+
+    /// Intent created for `reservation_id`, if any.
+    pub fn intent_for(&self, reservation_id: Uuid) -> Option<PaymentIntent> {
+        self.state()
+            .intents
+            .values()
+            .find(|intent| intent.reservation_id == reservation_id)
+            .cloned()
+    }
+
+    pub fn refunds(&self) -> Vec<PaymentRefund> {
+        self.state().refunds.clone()
+    }
+
+    /// Signs `payload` the way the provider signs its webhooks.
+    pub fn sign(&self, payload: &[u8]) -> String {
+        sign_payload(self.webhook_secret.as_bytes(), payload)
+    }
+
+    /// Webhook the provider sends about `intent`, e.g. `payment.succeeded`.
+    pub fn webhook_payload(event_id: &str, event_type: &str, intent: &PaymentIntent) -> Vec<u8> {
+        json!({
+            "id": event_id,
+            "type": event_type,
+            "data": {
+                "intent_id": intent.id,
+                "reservation_id": intent.reservation_id,
+                "amount": intent.amount,
+            },
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().expect("mock payment state lock poisoned")
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for MockPaymentProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn create_intent(
+        &self,
+        reservation_id: Uuid,
+        amount: Decimal,
+        currency: &str,
+    ) -> Result<PaymentIntent, PaymentError> {
+        let id = format!("mock_pi_{}", reservation_id.simple());
+        let intent = self
+            .state()
+            .intents
+            .entry(id.clone())
+            .or_insert_with(|| PaymentIntent {
+                id,
+                reservation_id,
+                amount,
+                currency: currency.to_string(),
+                status: PaymentIntentStatus::RequiresCapture,
+            })
+            .clone();
+        Ok(intent)
+    }
+
+    async fn capture(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError> {
+        let mut state = self.state();
+        let decline_reason = state.decline_reason.clone();
+        let intent = state
+            .intents
+            .get_mut(intent_id)
+            .ok_or_else(|| PaymentError::UnknownIntent(intent_id.to_string()))?;
+        if intent.status == PaymentIntentStatus::Captured {
+            return Ok(intent.clone());
+        }
+        if let Some(reason) = decline_reason {
+            intent.status = PaymentIntentStatus::Failed;
+            return Err(PaymentError::Declined(reason));
+        }
+        intent.status = PaymentIntentStatus::Captured;
+        Ok(intent.clone())
+    }
+
+    async fn refund(
+        &self,
+        intent_id: &str,
+        amount: Decimal,
+    ) -> Result<PaymentRefund, PaymentError> {
+        let mut state = self.state();
+        let intent = state
+            .intents
+            .get(intent_id)
+            .ok_or_else(|| PaymentError::UnknownIntent(intent_id.to_string()))?;
+        if intent.status != PaymentIntentStatus::Captured {
+            return Err(PaymentError::Provider(format!(
+                "Intent `{}` has not been captured",
+                intent_id
+            )));
+        }
+        let refunded: Decimal = state
+            .refunds
+            .iter()
+            .filter(|refund| refund.intent_id == intent_id)
+            .map(|refund| refund.amount)
+            .sum();
+        if refunded >= intent.amount {
+            return Err(PaymentError::AlreadyRefunded(intent_id.to_string()));
+        }
+        if refunded + amount > intent.amount {
+            return Err(PaymentError::Provider(format!(
+                "Refunds of intent `{}` would exceed the amount captured",
+                intent_id
+            )));
+        }
+        let refund = PaymentRefund {
+            id: format!("mock_re_{}", Uuid::new_v4().simple()),
+            intent_id: intent_id.to_string(),
+            amount,
+        };
+        state.refunds.push(refund.clone());
+        Ok(refund)
+    }
+
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<(), PaymentError> {
+        verify_payload(self.webhook_secret.as_bytes(), payload, signature)
+    }
+
+    fn parse_webhook(&self, payload: &[u8]) -> Result<WebhookEvent, PaymentError> {
+        let value: serde_json::Value = serde_json::from_slice(payload)
+            .map_err(|err| PaymentError::InvalidPayload(err.to_string()))?;
+        let webhook = MockWebhook::deserialize(&value)
+            .map_err(|err| PaymentError::InvalidPayload(err.to_string()))?;
+        let outcome = match webhook.event_type.as_str() {
+            "payment.succeeded" => Some(PaymentOutcome::Succeeded),
+            "payment.failed" => Some(PaymentOutcome::Failed),
+            _ => None,
+        };
+        Ok(WebhookEvent {
+            id: webhook.id,
+            event_type: webhook.event_type,
+            outcome,
+            intent_id: webhook.data.intent_id,
+            reservation_id: webhook.data.reservation_id,
+            amount: webhook.data.amount,
+            payload: value,
+        })
+    }
+}
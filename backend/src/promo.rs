@@ -0,0 +1,208 @@
+//! Promo codes take a percentage or a fixed amount off a reservation.
+
+use crate::error::AppError;
+use crate::model::{
+    event, event_object, promo_code, reservation, reservation_discount, reservation_item,
+};
+use crate::money::PRICE_SCALE;
+use crate::status::ReservationStatus;
+use chrono::NaiveDateTime;
+use sea_orm::prelude::Decimal;
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, QueryFilter,
+    QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Longest code accepted, matching the column width.
+pub const MAX_CODE_LENGTH: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountKind {
+    /// `amount` percent off the discounted seats.
+    Percent,
+    /// `amount` off the discounted seats, never more than they cost.
+    Fixed,
+}
+
+impl DiscountKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscountKind::Percent => "percent",
+            DiscountKind::Fixed => "fixed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "percent" => Some(DiscountKind::Percent),
+            "fixed" => Some(DiscountKind::Fixed),
+            _ => None,
+        }
+    }
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// Amount `promo` takes off seats worth `eligible`, rounded to the cent.
+pub fn discount_amount(promo: &promo_code::Model, eligible: Decimal) -> Decimal {
+    match DiscountKind::parse(&promo.kind) {
+        Some(DiscountKind::Percent) => (eligible * promo.amount / Decimal::ONE_HUNDRED)
+            .round_dp(PRICE_SCALE)
+            .min(eligible),
+        Some(DiscountKind::Fixed) => promo.amount.min(eligible),
+        None => Decimal::ZERO,
+    }
+}
+
+/// What `discount` still takes off the `kept` seats once the others are cancelled.
+pub async fn remaining_discount(
+    db: &impl ConnectionTrait,
+    discount: &reservation_discount::Model,
+    kept: &[&reservation_item::Model],
+) -> Result<Decimal, DbErr> {
+    if kept.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    let subtotal: Decimal = kept.iter().map(|item| item.price_at_booking).sum();
+    let promo = match discount.promo_code_id {
+        Some(promo_code_id) => {
+            promo_code::Entity::find_by_id(promo_code_id)
+                .one(db)
+                .await?
+        }
+        None => None,
+    };
+    // Without its promo code the discount can only shrink with the seats.
+    let Some(promo) = promo else {
+        return Ok(discount.amount.min(subtotal));
+    };
+
+    let eligible = match promo.section_id {
+        Some(section_id) => {
+            let seats: Vec<Uuid> = event_object::Entity::find()
+                .select_only()
+                .column(event_object::Column::Id)
+                .filter(
+                    event_object::Column::Id.is_in(kept.iter().map(|item| item.event_object_id)),
+                )
+                .filter(event_object::Column::SectionId.eq(section_id))
+                .into_tuple()
+                .all(db)
+                .await?;
+            kept.iter()
+                .filter(|item| seats.contains(&item.event_object_id))
+                .map(|item| item.price_at_booking)
+                .sum()
+        }
+        None => subtotal,
+    };
+    Ok(discount_amount(&promo, eligible).min(discount.amount))
+}
+
+const REDEEMED_STATUSES: [ReservationStatus; 3] = [
+    ReservationStatus::Pending,
+    ReservationStatus::Confirmed,
+    ReservationStatus::RefundPending,
+];
+
+#[derive(FromQueryResult)]
+struct PromoUses {
+    uses: i64,
+    user_uses: Option<i64>,
+}
+
+/// Redeems `code` inside the transaction that inserts the reservation.
+pub async fn redeem_promo_code(
+    db: &impl ConnectionTrait,
+    event: &event::Model,
+    user_id: &str,
+    code: &str,
+    lines: &[(Option<Uuid>, Decimal)],
+    now: NaiveDateTime,
+) -> Result<(promo_code::Model, Decimal), AppError> {
+    let code = normalize_code(code);
+    let invalid = || AppError::Validation(format!("Promo code `{}` is not valid", code));
+    let promo = promo_code::Entity::find()
+        .filter(promo_code::Column::WorkspaceId.eq(event.workspace_id))
+        .filter(promo_code::Column::Code.eq(code.as_str()))
+        .filter(
+            Condition::any()
+                .add(promo_code::Column::EventId.is_null())
+                .add(promo_code::Column::EventId.eq(event.id)),
+        )
+        .lock_exclusive()
+        .one(db)
+        .await?
+        .ok_or_else(invalid)?;
+    if promo.valid_from.is_some_and(|valid_from| now < valid_from)
+        || promo
+            .valid_until
+            .is_some_and(|valid_until| valid_until <= now)
+    {
+        return Err(invalid());
+    }
+
+    let eligible: Decimal = lines
+        .iter()
+        .filter(|(section_id, _)| promo.section_id.is_none() || *section_id == promo.section_id)
+        .map(|(_, price)| *price)
+        .sum();
+    if eligible.is_zero() {
+        return Err(AppError::Validation(format!(
+            "Promo code `{}` does not apply to the selected seats",
+            code
+        )));
+    }
+
+    if promo.max_uses.is_some() || promo.max_uses_per_user.is_some() {
+        let uses = promo_uses(db, promo.id, user_id).await?;
+        let exhausted =
+            |limit: Option<i32>, uses: i64| limit.is_some_and(|limit| uses >= limit as i64);
+        if exhausted(promo.max_uses, uses.uses) {
+            return Err(AppError::Conflict(format!(
+                "Promo code `{}` has been used up",
+                code
+            )));
+        }
+        if exhausted(promo.max_uses_per_user, uses.user_uses.unwrap_or(0)) {
+            return Err(AppError::Conflict(format!(
+                "You have already used promo code `{}`",
+                code
+            )));
+        }
+    }
+
+    let amount = discount_amount(&promo, eligible);
+    Ok((promo, amount))
+}
+
+/// Live reservations that redeemed `promo_code_id`, in total and by `user_id`.
+async fn promo_uses(
+    db: &impl ConnectionTrait,
+    promo_code_id: Uuid,
+    user_id: &str,
+) -> Result<PromoUses, DbErr> {
+    let by_user: SimpleExpr =
+        Func::sum(Expr::case(reservation::Column::UserId.eq(user_id), 1).finally(0)).into();
+    let uses = reservation_discount::Entity::find()
+        .select_only()
+        .column_as(reservation_discount::Column::Id.count(), "uses")
+        .column_as(by_user, "user_uses")
+        .inner_join(reservation::Entity)
+        .filter(reservation_discount::Column::PromoCodeId.eq(promo_code_id))
+        .filter(reservation::Column::Status.is_in(REDEEMED_STATUSES.map(|status| status.as_str())))
+        .into_model::<PromoUses>()
+        .one(db)
+        .await?;
+    Ok(uses.unwrap_or(PromoUses {
+        uses: 0,
+        user_uses: None,
+    }))
+}
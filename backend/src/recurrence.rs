@@ -0,0 +1,132 @@
+//! RRULE-style recurrence rules for event series.
+
+use crate::error::AppError;
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Upper bound on the occurrences of one series, each being a full event.
+pub const MAX_OCCURRENCES: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(Frequency::Daily),
+            "weekly" => Some(Frequency::Weekly),
+            "monthly" => Some(Frequency::Monthly),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    /// Start of the first occurrence; later ones keep its time of day.
+    pub starts_at: NaiveDateTime,
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    /// Last moment an occurrence may start, inclusive.
+    pub until: Option<NaiveDateTime>,
+    pub exclusions: Vec<NaiveDate>,
+}
+
+impl Recurrence {
+    /// Start times of every occurrence, up to [`MAX_OCCURRENCES`].
+    pub fn occurrences(&self) -> Result<Vec<NaiveDateTime>, AppError> {
+        if self.interval == 0 {
+            return Err(AppError::Validation(
+                "`interval` must be at least 1".to_string(),
+            ));
+        }
+        if self.count.is_none() && self.until.is_none() {
+            return Err(AppError::Validation(
+                "Either `count` or `until` is required".to_string(),
+            ));
+        }
+        let too_many = || {
+            AppError::Validation(format!(
+                "A series can have at most {} occurrences",
+                MAX_OCCURRENCES
+            ))
+        };
+        if self
+            .count
+            .is_some_and(|count| count as usize > MAX_OCCURRENCES)
+        {
+            return Err(too_many());
+        }
+
+        let mut starts = Vec::new();
+        let mut generated = 0;
+        for step in 0.. {
+            if self.count.is_some_and(|count| generated >= count) {
+                break;
+            }
+            let candidate = match self.nth(step) {
+                Step::Occurs(candidate) => candidate,
+                Step::Skipped => continue,
+                Step::OutOfRange => break,
+            };
+            if self.until.is_some_and(|until| candidate > until) {
+                break;
+            }
+            generated += 1;
+            if !self.exclusions.contains(&candidate.date()) {
+                if starts.len() == MAX_OCCURRENCES {
+                    return Err(too_many());
+                }
+                starts.push(candidate);
+            }
+        }
+        Ok(starts)
+    }
+
+    fn nth(&self, step: u32) -> Step {
+        let Some(step) = step.checked_mul(self.interval) else {
+            return Step::OutOfRange;
+        };
+        let candidate = match self.frequency {
+            Frequency::Daily => self.starts_at.checked_add_days(Days::new(step.into())),
+            Frequency::Weekly => self
+                .starts_at
+                .checked_add_days(Days::new(u64::from(step) * 7)),
+            Frequency::Monthly => match self.starts_at.checked_add_months(Months::new(step)) {
+                // `checked_add_months` clamps the 31st to the end of shorter
+                // months, which RRULE skips instead.
+                Some(candidate) if candidate.day() != self.starts_at.day() => {
+                    return Step::Skipped;
+                }
+                candidate => candidate,
+            },
+        };
+        match candidate {
+            Some(candidate) => Step::Occurs(candidate),
+            None => Step::OutOfRange,
+        }
+    }
+}
+
+enum Step {
+    Occurs(NaiveDateTime),
+    /// The month has no such day.
+    Skipped,
+    /// Past the dates chrono can represent.
+    OutOfRange,
+}
@@ -0,0 +1,91 @@
+//! Refunds of cancelled seats, less the cancellation fees of their event.
+
+use crate::error::AppError;
+use crate::model::payment_refund;
+use crate::money::PRICE_SCALE;
+use crate::payment::{PaymentError, PaymentProvider};
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+use uuid::Uuid;
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RefundPolicy {
+    /// Percent of a seat's price kept when it is cancelled.
+    pub fee_percent: Decimal,
+    /// Amount kept for every cancelled seat, on top of `fee_percent`.
+    pub fee_per_seat: Decimal,
+}
+
+impl RefundPolicy {
+    /// Reads the policy from the `refund` key of an event's `settings`.
+    pub fn from_settings(settings: Option<&Value>) -> Result<Self, AppError> {
+        let Some(policy) = settings.and_then(|settings| settings.get("refund")) else {
+            return Ok(Self::default());
+        };
+        let policy = Self::deserialize(policy).map_err(|err| {
+            AppError::Validation(format!("`settings.refund` is invalid: {}", err))
+        })?;
+        if policy.fee_percent.is_sign_negative() || policy.fee_percent > Decimal::ONE_HUNDRED {
+            return Err(AppError::Validation(
+                "`settings.refund.fee_percent` must be between 0 and 100".to_string(),
+            ));
+        }
+        if policy.fee_per_seat.is_sign_negative() {
+            return Err(AppError::Validation(
+                "`settings.refund.fee_per_seat` must not be negative".to_string(),
+            ));
+        }
+        Ok(policy)
+    }
+
+    /// Splits `price` into the amount refunded and the fee kept.
+    pub fn split(&self, price: Decimal) -> (Decimal, Decimal) {
+        let fee = ((price * self.fee_percent / Decimal::ONE_HUNDRED).round_dp(PRICE_SCALE)
+            + self.fee_per_seat)
+            .min(price);
+        (price - fee, fee)
+    }
+}
+
+/// Gives `amount` of a payment that bought nothing back, once per intent.
+pub async fn refund_capture(
+    db: &impl ConnectionTrait,
+    payments: &dyn PaymentProvider,
+    reservation_id: Uuid,
+    intent_id: &str,
+    amount: Decimal,
+) -> Result<bool, AppError> {
+    let recorded = payment_refund::Entity::find()
+        .filter(payment_refund::Column::PaymentProvider.eq(payments.name()))
+        .filter(payment_refund::Column::PaymentIntentId.eq(intent_id))
+        .one(db)
+        .await?;
+    if recorded.is_some() {
+        return Ok(false);
+    }
+    let refund = match payments.refund(intent_id, amount).await {
+        Ok(refund) => refund,
+        Err(PaymentError::AlreadyRefunded(_)) => {
+            warn!("Payment intent {} had already been refunded", intent_id);
+            return Ok(false);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    payment_refund::Entity::insert(payment_refund::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        reservation_id: Set(reservation_id),
+        payment_provider: Set(payments.name().to_string()),
+        payment_intent_id: Set(intent_id.to_string()),
+        payment_refund_id: Set(refund.id),
+        amount: Set(amount),
+        ..Default::default()
+    })
+    .exec_without_returning(db)
+    .await?;
+    Ok(true)
+}
@@ -3,9 +3,22 @@ use tracing::{Instrument, info_span};
 
 use crate::{app::AppState, error::AppError};
 
+pub mod check_in;
 pub mod event;
+pub mod event_object;
+pub mod event_status;
 pub mod form;
+pub mod form_submission;
+pub mod layout;
+pub mod live;
+pub mod member;
+pub mod payment_webhook;
+pub mod promo_code;
+pub mod reservation;
 pub mod section;
+pub mod series;
+pub mod ticket;
+pub mod trash;
 pub mod workspace;
 
 #[tracing::instrument]
@@ -0,0 +1,232 @@
+use crate::access::{Permission, authorize_event};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::check_in::{CheckInRequest, CheckInResponse, CheckInSummaryResponse, GateCheckIns};
+use crate::error::AppError;
+use crate::model::{check_in, ticket};
+use crate::status::EventStatus;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use chrono::Utc;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, EntityTrait, FromQueryResult, IntoActiveModel, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+const MAX_GATE_LENGTH: usize = 64;
+const MAX_DEVICE_LENGTH: usize = 128;
+
+pub fn check_in_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(check_in_ticket))
+        .routes(routes!(undo_check_in))
+        .routes(routes!(check_in_summary))
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/{event_id}/check_ins",
+    tag = "check_in",
+    request_body = CheckInRequest,
+    responses(
+        (status = 200, body = CheckInResponse),
+        (status = 400, description = "The token is not a valid ticket"),
+        (status = 409, description = "The ticket cannot be let in, e.g. it was already checked in")
+    )
+)]
+async fn check_in_ticket(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(event_id): Path<Uuid>,
+    Json(payload): Json<CheckInRequest>,
+) -> Result<Json<CheckInResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, event_id, Permission::Sell).await?;
+
+    let gate = payload.gate.trim();
+    if gate.is_empty() || gate.len() > MAX_GATE_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Gate must be between 1 and {} characters",
+            MAX_GATE_LENGTH
+        )));
+    }
+    let device = payload
+        .device
+        .as_deref()
+        .map(str::trim)
+        .filter(|device| !device.is_empty());
+    if device.is_some_and(|device| device.len() > MAX_DEVICE_LENGTH) {
+        return Err(AppError::Validation(format!(
+            "Device must be at most {} characters",
+            MAX_DEVICE_LENGTH
+        )));
+    }
+
+    let claims = app_state
+        .tickets
+        .verify(&payload.token)
+        .ok_or_else(|| AppError::Validation("Ticket is not valid".to_string()))?;
+    if claims.event_id != event.id {
+        return Err(AppError::Conflict(format!(
+            "Ticket {} is for another event",
+            claims.seat_label
+        )));
+    }
+    if event.status == EventStatus::Cancelled.as_str() {
+        return Err(AppError::Conflict("Event has been cancelled".to_string()));
+    }
+    let ticket = ticket::Entity::find_by_id(claims.ticket_id)
+        .filter(ticket::Column::EventId.eq(event.id))
+        .one(&*app_state.db)
+        .await?
+        .ok_or_else(|| {
+            AppError::Conflict(format!("Ticket {} no longer exists", claims.seat_label))
+        })?;
+    if ticket.revoked_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Ticket {} has been cancelled",
+            ticket.seat_label
+        )));
+    }
+
+    let now = Utc::now().naive_utc();
+    let record = check_in::Model {
+        id: Uuid::new_v4(),
+        ticket_id: ticket.id,
+        event_id: event.id,
+        gate: gate.to_string(),
+        device: device.map(str::to_string),
+        operator_id: user.id().to_string(),
+        checked_in_at: now,
+        undone_at: None,
+        undone_by: None,
+        created_at: now,
+        updated_at: now,
+    };
+    // The partial unique index on `ticket_id` lets only one scan of a ticket
+    // be in effect, even when two gates scan it at the same time.
+    let inserted = check_in::Entity::insert(record.clone().into_active_model())
+        .on_conflict(
+            OnConflict::column(check_in::Column::TicketId)
+                .target_and_where(Expr::col(check_in::Column::UndoneAt).is_null())
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&*app_state.db)
+        .await?;
+    if inserted == 0 {
+        let existing = check_in::Entity::find()
+            .filter(check_in::Column::TicketId.eq(ticket.id))
+            .filter(check_in::Column::UndoneAt.is_null())
+            .one(&*app_state.db)
+            .await?;
+        return Err(AppError::Conflict(match existing {
+            Some(existing) => format!(
+                "Ticket {} was already checked in at gate {} at {} UTC",
+                ticket.seat_label,
+                existing.gate,
+                existing.checked_in_at.format("%Y-%m-%d %H:%M:%S")
+            ),
+            None => format!("Ticket {} was already checked in", ticket.seat_label),
+        }));
+    }
+
+    Ok(Json(CheckInResponse::from(record)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/{event_id}/check_ins/{check_in_id}/undo",
+    tag = "check_in",
+    responses(
+        (status = 200, body = CheckInResponse),
+        (status = 404, description = "No check-in in effect with this id")
+    )
+)]
+async fn undo_check_in(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path((event_id, check_in_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<CheckInResponse>, AppError> {
+    authorize_event(&app_state.db, &user, event_id, Permission::Sell).await?;
+
+    let undone = check_in::Entity::update_many()
+        .col_expr(
+            check_in::Column::UndoneAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .col_expr(check_in::Column::UndoneBy, Expr::value(user.id()))
+        .filter(check_in::Column::Id.eq(check_in_id))
+        .filter(check_in::Column::EventId.eq(event_id))
+        .filter(check_in::Column::UndoneAt.is_null())
+        .exec_with_returning(&*app_state.db)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound("Check-in not found".to_string()))?;
+    Ok(Json(CheckInResponse::from(undone)))
+}
+
+#[derive(FromQueryResult)]
+struct TicketCount {
+    tickets: i64,
+}
+
+#[derive(FromQueryResult)]
+struct GateCount {
+    gate: String,
+    checked_in: i64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{event_id}/check_ins/summary",
+    tag = "check_in",
+    responses((status = 200, body = CheckInSummaryResponse))
+)]
+async fn check_in_summary(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<CheckInSummaryResponse>, AppError> {
+    authorize_event(&app_state.db, &user, event_id, Permission::Sell).await?;
+
+    let tickets = ticket::Entity::find()
+        .select_only()
+        .column_as(ticket::Column::Id.count(), "tickets")
+        .filter(ticket::Column::EventId.eq(event_id))
+        .filter(ticket::Column::RevokedAt.is_null())
+        .into_model::<TicketCount>()
+        .one(&*app_state.db)
+        .await?
+        .map_or(0, |count| count.tickets as u64);
+    // Check-ins of revoked tickets are left out, as their tickets are.
+    let gates = check_in::Entity::find()
+        .select_only()
+        .column(check_in::Column::Gate)
+        .column_as(check_in::Column::Id.count(), "checked_in")
+        .inner_join(ticket::Entity)
+        .filter(check_in::Column::EventId.eq(event_id))
+        .filter(check_in::Column::UndoneAt.is_null())
+        .filter(ticket::Column::RevokedAt.is_null())
+        .group_by(check_in::Column::Gate)
+        .order_by_asc(check_in::Column::Gate)
+        .into_model::<GateCount>()
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        .map(|count| GateCheckIns {
+            gate: count.gate,
+            checked_in: count.checked_in as u64,
+        })
+        .collect::<Vec<_>>();
+
+    let checked_in = gates.iter().map(|gate| gate.checked_in).sum();
+    Ok(Json(CheckInSummaryResponse {
+        tickets,
+        checked_in,
+        remaining: tickets.saturating_sub(checked_in),
+        gates,
+    }))
+}
@@ -1,19 +1,188 @@
+use crate::access::{Permission, authorize_event, authorize_workspace};
 use crate::app::AppState;
-use crate::dto::event::{EventRequest, EventResponse, UpdateEventRequest};
+use crate::auth::AuthUser;
+use crate::dto::event::{
+    CloneEventRequest, EventDetailResponse, EventDetailSection, EventListQuery, EventPage,
+    EventRequest, EventResponse, EventSort, UpdateEventRequest,
+};
+use crate::dto::layout::LayoutObject;
 use crate::dto::workspace::DeleteResponse;
 use crate::error::AppError;
-use crate::model::event;
+use crate::event_copy::{EventCopy, EventTemplate};
+use crate::model::{event, event_object, event_object_position, form, reservation, section};
+use crate::money::{DEFAULT_CURRENCY, validate_currency};
+use crate::pagination::{Cursor, SortOrder, page_size};
+use crate::refund::RefundPolicy;
+use crate::status::{EventStatus, ReservationStatus};
+use crate::timezone::{DEFAULT_TIMEZONE, to_utc, validate_range, validate_timezone};
+use crate::trash::{SoftDelete, move_to_trash};
 use axum::extract::{Path, Query};
 use axum::{Json, extract::State};
+use chrono::NaiveDateTime;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel};
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr, LikeExpr};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IntoActiveModel, ModelTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait, Value,
+};
 use std::collections::HashMap;
 use std::iter::Iterator;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 pub fn event_routes() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(get_event, create_event, delete_event, update_event))
+    OpenApiRouter::new()
+        .routes(routes!(get_event, create_event, delete_event, update_event))
+        .routes(routes!(list_events))
+        .routes(routes!(get_event_detail))
+        .routes(routes!(get_public_event))
+        .routes(routes!(clone_event))
+        .routes(routes!(restore_event))
+}
+
+const CURSOR_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+#[utoipa::path(
+    get,
+    path = "/workspace/{id}/events",
+    tag = "event",
+    params(EventListQuery),
+    responses((status = 200, body = EventPage))
+)]
+async fn list_events(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<EventListQuery>,
+) -> Result<Json<EventPage>, AppError> {
+    authorize_workspace(&app_state.db, &user, id, Permission::View).await?;
+
+    let sort = query.sort.unwrap_or_default();
+    let order = query.order.unwrap_or_default();
+    let limit = page_size(query.limit);
+    let from = query.from.map(to_utc);
+    let to = query.to.map(to_utc);
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(AppError::BadRequest(
+            "`from` must not be after `to`".to_string(),
+        ));
+    }
+
+    let mut select = event::Entity::find_live().filter(event::Column::WorkspaceId.eq(id));
+    if let Some(from) = from {
+        // Events without an end time are treated as ending when they start.
+        select = select.filter(
+            Condition::any().add(event::Column::EndsAt.gte(from)).add(
+                Condition::all()
+                    .add(event::Column::EndsAt.is_null())
+                    .add(event::Column::StartsAt.gte(from)),
+            ),
+        );
+    }
+    if let Some(to) = to {
+        select = select.filter(event::Column::StartsAt.lte(to));
+    }
+    if let Some(search) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let pattern = format!("%{}%", escape_like(search));
+        select = select.filter(
+            Expr::col((event::Entity, event::Column::Title))
+                .ilike(LikeExpr::new(pattern).escape('\\')),
+        );
+    }
+    if let Some(cursor) = &query.cursor {
+        let cursor = Cursor::decode(cursor, sort.as_str())?;
+        select = select.filter(after_cursor(sort, order, &cursor)?);
+    }
+
+    let mut events = select
+        .order_by(sort_column(sort), order.into())
+        .order_by(event::Column::Id, order.into())
+        .limit(limit + 1)
+        .all(&*app_state.db)
+        .await?;
+
+    let next_cursor = if events.len() as u64 > limit {
+        events.truncate(limit as usize);
+        events.last().map(|last| {
+            Cursor {
+                sort: sort.as_str().to_string(),
+                value: sort_key(sort, last),
+                id: last.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(EventPage {
+        items: events.into_iter().map(EventResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+fn sort_column(sort: EventSort) -> event::Column {
+    match sort {
+        EventSort::StartsAt => event::Column::StartsAt,
+        EventSort::Title => event::Column::Title,
+        EventSort::CreatedAt => event::Column::CreatedAt,
+    }
+}
+
+fn sort_key(sort: EventSort, event: &event::Model) -> Option<String> {
+    match sort {
+        EventSort::StartsAt => event
+            .starts_at
+            .map(|starts_at| starts_at.format(CURSOR_TIME_FORMAT).to_string()),
+        EventSort::Title => Some(event.title.clone()),
+        EventSort::CreatedAt => Some(event.created_at.format(CURSOR_TIME_FORMAT).to_string()),
+    }
+}
+
+/// Rows strictly after `cursor`, with `NULL` start times where Postgres sorts them.
+fn after_cursor(sort: EventSort, order: SortOrder, cursor: &Cursor) -> Result<Condition, AppError> {
+    let column = sort_column(sort);
+    let id = event::Column::Id;
+    let condition = match (&cursor.value, order) {
+        (Some(raw), SortOrder::Asc) => {
+            let value = cursor_value(sort, raw)?;
+            Condition::any()
+                .add(column.gt(value.clone()))
+                .add(Condition::all().add(column.eq(value)).add(id.gt(cursor.id)))
+                .add(column.is_null())
+        }
+        (Some(raw), SortOrder::Desc) => {
+            let value = cursor_value(sort, raw)?;
+            Condition::any()
+                .add(column.lt(value.clone()))
+                .add(Condition::all().add(column.eq(value)).add(id.lt(cursor.id)))
+        }
+        (None, SortOrder::Asc) => Condition::all().add(column.is_null()).add(id.gt(cursor.id)),
+        (None, SortOrder::Desc) => Condition::any()
+            .add(column.is_not_null())
+            .add(Condition::all().add(column.is_null()).add(id.lt(cursor.id))),
+    };
+    Ok(condition)
+}
+
+fn cursor_value(sort: EventSort, raw: &str) -> Result<Value, AppError> {
+    match sort {
+        EventSort::Title => Ok(Value::from(raw.to_string())),
+        EventSort::StartsAt | EventSort::CreatedAt => raw
+            .parse::<NaiveDateTime>()
+            .map(Value::from)
+            .map_err(|_| AppError::BadRequest("Invalid `cursor`".to_string())),
+    }
+}
+
+fn escape_like(search: &str) -> String {
+    search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
 #[utoipa::path(
@@ -24,13 +193,99 @@ pub fn event_routes() -> OpenApiRouter<AppState> {
 )]
 pub async fn get_event(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<Json<EventResponse>, AppError> {
-    let event = event::Entity::find_by_id(id)
+    let event = authorize_event(&app_state.db, &user, id, Permission::View).await?;
+    Ok(Json(EventResponse::from(event)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/full",
+    tag = "event",
+    responses((status = 200, body = EventDetailResponse))
+)]
+async fn get_event_detail(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventDetailResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, id, Permission::View).await?;
+    Ok(Json(event_detail(&app_state, event).await?))
+}
+
+/// Reads a public event without signing in; others are not found.
+#[utoipa::path(
+    get,
+    path = "/public/event/{id}",
+    tag = "event",
+    security(()),
+    responses(
+        (status = 200, body = EventDetailResponse),
+        (status = 404, description = "No public event with this id")
+    )
+)]
+async fn get_public_event(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventDetailResponse>, AppError> {
+    let event = event::Entity::find_live_by_id(id)
         .one(&*app_state.db)
         .await?
+        .filter(|event| EventStatus::parse(&event.status).is_some_and(|status| status.is_public()))
         .ok_or(AppError::NotFound("Event not found".to_string()))?;
-    Ok(Json(EventResponse::from(event)))
+    Ok(Json(event_detail(&app_state, event).await?))
+}
+
+async fn event_detail(
+    app_state: &AppState,
+    event: event::Model,
+) -> Result<EventDetailResponse, AppError> {
+    // One query per relation, whatever the number of sections or objects.
+    // Objects of deleted sections are left out along with their section.
+    let sections = event
+        .find_related(section::Entity)
+        .filter(section::Column::DeletedAt.is_null())
+        .order_by_asc(section::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+    let objects = event
+        .find_related(event_object::Entity)
+        .find_also_related(event_object_position::Entity)
+        .order_by_asc(event_object::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+    let forms = event
+        .find_related(form::Entity)
+        .filter(form::Column::DeletedAt.is_null())
+        .order_by_asc(form::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+
+    let mut by_section: HashMap<Uuid, Vec<LayoutObject>> = HashMap::new();
+    let mut unsectioned_objects = Vec::new();
+    for (object, position) in objects {
+        let section_id = object.section_id;
+        let object = LayoutObject::new(object, position);
+        match section_id {
+            Some(section_id) => by_section.entry(section_id).or_default().push(object),
+            None => unsectioned_objects.push(object),
+        }
+    }
+
+    Ok(EventDetailResponse {
+        event: EventResponse::from(event),
+        sections: sections
+            .into_iter()
+            .map(|section| EventDetailSection {
+                objects: by_section.remove(&section.id).unwrap_or_default(),
+                section,
+            })
+            .collect(),
+        unsectioned_objects,
+        forms,
+    })
 }
 
 #[utoipa::path(
@@ -42,21 +297,80 @@ pub async fn get_event(
 )]
 async fn create_event(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<EventRequest>,
 ) -> Result<Json<EventResponse>, AppError> {
+    authorize_workspace(&app_state.db, &user, body.workspace_id, Permission::Edit).await?;
+    let starts_at = body.starts_at.map(to_utc);
+    let ends_at = body.ends_at.map(to_utc);
+    validate_range(starts_at, ends_at)?;
+    RefundPolicy::from_settings(body.settings.as_ref())?;
     let event = event::ActiveModel {
         title: Set(body.title),
         workspace_id: Set(body.workspace_id),
         description: Set(body.description),
-        starts_at: Set(body.starts_at),
-        ends_at: Set(body.ends_at),
+        starts_at: Set(starts_at),
+        ends_at: Set(ends_at),
+        timezone: Set(match body.timezone {
+            Some(timezone) => validate_timezone(&timezone)?.name().to_string(),
+            None => DEFAULT_TIMEZONE.to_string(),
+        }),
+        currency: Set(match body.currency {
+            Some(currency) => validate_currency(&currency)?,
+            None => DEFAULT_CURRENCY.to_string(),
+        }),
         settings: Set(body.settings),
+        status: Set(EventStatus::Draft.as_str().to_string()),
         ..Default::default()
     };
     let event = event.insert(&*app_state.db).await?;
     Ok(Json(EventResponse::from(event)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/event/{id}/clone",
+    tag = "event",
+    request_body = CloneEventRequest,
+    responses((status = 200, body = EventResponse))
+)]
+async fn clone_event(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CloneEventRequest>,
+) -> Result<Json<EventResponse>, AppError> {
+    let source = authorize_event(&app_state.db, &user, id, Permission::View).await?;
+    let workspace_id = body.workspace_id.unwrap_or(source.workspace_id);
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::Edit).await?;
+
+    let starts_at = body.starts_at.map(to_utc);
+    let ends_at = match (starts_at, body.ends_at.map(to_utc)) {
+        (_, Some(ends_at)) => Some(ends_at),
+        (Some(starts_at), None) => match (source.starts_at, source.ends_at) {
+            (Some(from), Some(to)) => Some(starts_at + (to - from)),
+            _ => None,
+        },
+        (None, None) => source.ends_at,
+    };
+    let copy = EventCopy {
+        workspace_id,
+        title: body.title.unwrap_or_else(|| source.title.clone()),
+        starts_at: starts_at.or(source.starts_at),
+        ends_at,
+        series_id: None,
+    };
+    validate_range(copy.starts_at, copy.ends_at)?;
+
+    let txn = app_state.db.begin().await?;
+    let event = EventTemplate::load(&txn, source)
+        .await?
+        .copy(&txn, copy)
+        .await?;
+    txn.commit().await?;
+    Ok(Json(EventResponse::from(event)))
+}
+
 #[utoipa::path(
     delete,
     path = "/event",
@@ -64,10 +378,14 @@ async fn create_event(
     params(
         ("event_id" = Uuid, Query, description = "ID of the event to delete")
     ),
-    responses((status = 200, body = DeleteResponse))
+    responses(
+        (status = 200, body = DeleteResponse),
+        (status = 409, description = "The event has paid reservations")
+    )
 )]
 async fn delete_event(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<DeleteResponse>, AppError> {
     let event_id: Uuid = params
@@ -77,14 +395,51 @@ async fn delete_event(
         ))?
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid UUID for `event_id`".to_string()))?;
+    let event = authorize_event(&app_state.db, &user, event_id, Permission::Edit).await?;
 
-    let result = event::Entity::delete_by_id(event_id)
-        .exec(&*app_state.db)
+    // Buyers keep access to their seats, tickets and refunds, so an event that
+    // sold anything stays out of the trash.
+    let paid = reservation::Entity::find()
+        .filter(reservation::Column::EventId.eq(event.id))
+        .filter(reservation::Column::Status.is_in([
+            ReservationStatus::Confirmed.as_str(),
+            ReservationStatus::RefundPending.as_str(),
+        ]))
+        .one(&*app_state.db)
         .await?;
+    if paid.is_some() {
+        return Err(AppError::Conflict(
+            "Event has paid reservations and cannot be deleted".to_string(),
+        ));
+    }
 
-    Ok(Json(DeleteResponse {
-        rows_affected: result.rows_affected,
-    }))
+    let rows_affected = move_to_trash::<event::Entity>(&*app_state.db, event_id).await?;
+
+    Ok(Json(DeleteResponse { rows_affected }))
+}
+
+/// Brings an event back from the trash with its sections and forms.
+#[utoipa::path(
+    post,
+    path = "/event/{id}/restore",
+    tag = "event",
+    responses((status = 200, body = EventResponse))
+)]
+async fn restore_event(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventResponse>, AppError> {
+    let event = event::Entity::find_deleted_by_id(id)
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Event not in trash".to_string()))?;
+    authorize_workspace(&app_state.db, &user, event.workspace_id, Permission::Edit).await?;
+
+    let mut event = event.into_active_model();
+    event.deleted_at = Set(None);
+    let event = event.update(&*app_state.db).await?;
+    Ok(Json(EventResponse::from(event)))
 }
 
 #[utoipa::path(
@@ -96,12 +451,11 @@ async fn delete_event(
 )]
 async fn update_event(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<UpdateEventRequest>,
 ) -> Result<Json<EventResponse>, AppError> {
-    let mut event = event::Entity::find_by_id(body.id)
-        .one(&*app_state.db)
+    let mut event = authorize_event(&app_state.db, &user, body.id, Permission::Edit)
         .await?
-        .ok_or(AppError::NotFound("Event not found".to_string()))?
         .into_active_model();
 
     if let Some(title) = body.title {
@@ -111,14 +465,25 @@ async fn update_event(
         event.description = Set(Some(description));
     }
     if let Some(starts_at) = body.starts_at {
-        event.starts_at = Set(Some(starts_at));
+        event.starts_at = Set(Some(to_utc(starts_at)));
     }
     if let Some(ends_at) = body.ends_at {
-        event.ends_at = Set(Some(ends_at));
+        event.ends_at = Set(Some(to_utc(ends_at)));
+    }
+    validate_range(*event.starts_at.as_ref(), *event.ends_at.as_ref())?;
+    if let Some(timezone) = body.timezone {
+        event.timezone = Set(validate_timezone(&timezone)?.name().to_string());
+    }
+    if let Some(currency) = body.currency {
+        event.currency = Set(validate_currency(&currency)?);
     }
     if let Some(settings) = body.settings {
+        RefundPolicy::from_settings(Some(&settings))?;
         event.settings = Set(Some(settings));
     }
+    if event.series_id.as_ref().is_some() {
+        event.series_detached = Set(true);
+    }
 
     let updated_event = event.update(&*app_state.db).await?;
     Ok(Json(EventResponse::from(updated_event)))
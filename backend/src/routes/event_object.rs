@@ -0,0 +1,403 @@
+use crate::access::{Permission, authorize_event};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::event_object::{
+    BulkEventObjectRequest, EventObjectRequest, EventObjectResponse, UpdateEventObjectRequest,
+};
+use crate::dto::workspace::DeleteResponse;
+use crate::error::AppError;
+use crate::model::{event_object, reservation_item, section};
+use crate::status::SeatStatus;
+use crate::trash::SoftDelete;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait, TryIntoModel,
+};
+use std::collections::{HashMap, HashSet};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+const MAX_BULK_OBJECTS: usize = 5000;
+
+pub fn event_object_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_event_objects, create_event_object))
+        .routes(routes!(save_event_objects))
+        .routes(routes!(update_event_object, delete_event_object))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/objects",
+    tag = "event_object",
+    responses((status = 200, body = inline(Vec<EventObjectResponse>)))
+)]
+async fn get_event_objects(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EventObjectResponse>>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::View).await?;
+
+    let objects = event_object::Entity::find()
+        .filter(event_object::Column::EventId.eq(id))
+        .order_by_asc(event_object::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        .map(EventObjectResponse::from)
+        .collect();
+    Ok(Json(objects))
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/{id}/objects",
+    tag = "event_object",
+    request_body = EventObjectRequest,
+    responses((status = 200, body = EventObjectResponse))
+)]
+async fn create_event_object(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<EventObjectRequest>,
+) -> Result<Json<EventObjectResponse>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    validate_object_type(&body.object_type)?;
+    ensure_sections_in_event(&*app_state.db, id, body.section_id.into_iter().collect()).await?;
+
+    let object = event_object::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        event_id: Set(id),
+        object_type: Set(body.object_type),
+        section_id: Set(body.section_id),
+        label: Set(body.label),
+        is_enable: Set(body.is_enable.unwrap_or(true)),
+        status: Set(SeatStatus::Available.as_str().to_string()),
+        ..Default::default()
+    };
+    let object = object.insert(&*app_state.db).await?;
+    Ok(Json(EventObjectResponse::from(object)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/{id}/objects/bulk",
+    tag = "event_object",
+    request_body = BulkEventObjectRequest,
+    responses(
+        (status = 200, body = inline(Vec<EventObjectResponse>)),
+        (status = 409, description = "A held or sold object would be moved, retyped or relabelled")
+    )
+)]
+async fn save_event_objects(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<BulkEventObjectRequest>,
+) -> Result<Json<Vec<EventObjectResponse>>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+
+    if body.objects.len() > MAX_BULK_OBJECTS {
+        return Err(AppError::Validation(format!(
+            "At most {} objects can be saved at once",
+            MAX_BULK_OBJECTS
+        )));
+    }
+    for item in &body.objects {
+        validate_object_type(&item.object_type)?;
+    }
+
+    let existing_ids: Vec<Uuid> = body.objects.iter().filter_map(|item| item.id).collect();
+    if existing_ids.iter().collect::<HashSet<_>>().len() != existing_ids.len() {
+        return Err(AppError::Validation(
+            "The same object appears more than once".to_string(),
+        ));
+    }
+    let section_ids = body
+        .objects
+        .iter()
+        .filter_map(|item| item.section_id.flatten())
+        .collect();
+
+    let txn = app_state.db.begin().await?;
+    ensure_sections_in_event(&txn, id, section_ids).await?;
+
+    // Locked so a hold cannot take a seat while it is being changed.
+    let mut existing: HashMap<Uuid, event_object::Model> = if existing_ids.is_empty() {
+        HashMap::new()
+    } else {
+        event_object::Entity::find()
+            .filter(event_object::Column::EventId.eq(id))
+            .filter(event_object::Column::Id.is_in(existing_ids.clone()))
+            .lock_exclusive()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|object| (object.id, object))
+            .collect()
+    };
+    if existing.len() != existing_ids.len() {
+        return Err(AppError::Validation(
+            "Some objects do not belong to this event".to_string(),
+        ));
+    }
+
+    // Saved objects are answered in the order of the request, so the slots of
+    // the written ones are filled from what the inserts return.
+    let mut saved: Vec<Option<event_object::Model>> = Vec::with_capacity(body.objects.len());
+    let mut updated = Vec::new();
+    let mut created = Vec::new();
+    for item in body.objects {
+        match item.id.and_then(|object_id| existing.remove(&object_id)) {
+            Some(object) => {
+                let changed = event_object::Model {
+                    object_type: item.object_type,
+                    section_id: item.section_id.unwrap_or(object.section_id),
+                    label: item.label.unwrap_or(object.label.clone()),
+                    is_enable: item.is_enable.unwrap_or(object.is_enable),
+                    ..object.clone()
+                };
+                if changed == object {
+                    saved.push(Some(object));
+                    continue;
+                }
+                ensure_movable(&object, &changed)?;
+                updated.push((
+                    saved.len(),
+                    event_object::ActiveModel {
+                        id: Set(changed.id),
+                        event_id: Set(changed.event_id),
+                        object_type: Set(changed.object_type),
+                        section_id: Set(changed.section_id),
+                        label: Set(changed.label),
+                        is_enable: Set(changed.is_enable),
+                        status: Set(changed.status),
+                        ..Default::default()
+                    },
+                ));
+            }
+            None => created.push((
+                saved.len(),
+                event_object::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    event_id: Set(id),
+                    object_type: Set(item.object_type),
+                    section_id: Set(item.section_id.flatten()),
+                    label: Set(item.label.flatten()),
+                    is_enable: Set(item.is_enable.unwrap_or(true)),
+                    status: Set(SeatStatus::Available.as_str().to_string()),
+                    ..Default::default()
+                },
+            )),
+        }
+        saved.push(None);
+    }
+    if !updated.is_empty() {
+        let (slots, updated): (Vec<_>, Vec<_>) = updated.into_iter().unzip();
+        let objects = event_object::Entity::insert_many(updated)
+            .on_conflict(
+                OnConflict::column(event_object::Column::Id)
+                    .update_columns([
+                        event_object::Column::ObjectType,
+                        event_object::Column::SectionId,
+                        event_object::Column::Label,
+                        event_object::Column::IsEnable,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning_many(&txn)
+            .await?;
+        fill_slots(&mut saved, slots, objects);
+    }
+    if !created.is_empty() {
+        let (slots, created): (Vec<_>, Vec<_>) = created.into_iter().unzip();
+        let objects = event_object::Entity::insert_many(created)
+            .exec_with_returning_many(&txn)
+            .await?;
+        fill_slots(&mut saved, slots, objects);
+    }
+    txn.commit().await?;
+
+    Ok(Json(
+        saved
+            .into_iter()
+            .flatten()
+            .map(EventObjectResponse::from)
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/{id}/objects/{object_id}",
+    tag = "event_object",
+    request_body = UpdateEventObjectRequest,
+    responses(
+        (status = 200, body = EventObjectResponse),
+        (status = 409, description = "A held or sold object would be moved, retyped or relabelled")
+    )
+)]
+async fn update_event_object(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path((id, object_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateEventObjectRequest>,
+) -> Result<Json<EventObjectResponse>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    if let Some(object_type) = &body.object_type {
+        validate_object_type(object_type)?;
+    }
+
+    let txn = app_state.db.begin().await?;
+    if let Some(Some(section_id)) = body.section_id {
+        ensure_sections_in_event(&txn, id, HashSet::from([section_id])).await?;
+    }
+    // Locked like the bulk save so a hold cannot take the seat meanwhile.
+    let current = event_object::Entity::find_by_id(object_id)
+        .filter(event_object::Column::EventId.eq(id))
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or(AppError::NotFound("Event object not found".to_string()))?;
+    let mut object = current.clone().into_active_model();
+
+    if let Some(object_type) = body.object_type {
+        object.object_type = Set(object_type);
+    }
+    if let Some(section_id) = body.section_id {
+        object.section_id = Set(section_id);
+    }
+    if let Some(label) = body.label {
+        object.label = Set(label);
+    }
+    if let Some(is_enable) = body.is_enable {
+        object.is_enable = Set(is_enable);
+    }
+    ensure_movable(&current, &object.clone().try_into_model()?)?;
+
+    let object = object.update(&txn).await?;
+    txn.commit().await?;
+    Ok(Json(EventObjectResponse::from(object)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/event/{id}/objects/{object_id}",
+    tag = "event_object",
+    responses(
+        (status = 200, body = DeleteResponse),
+        (status = 409, description = "The object is held, sold or has been booked before; disable it instead")
+    )
+)]
+async fn delete_event_object(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path((id, object_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    let object = find_event_object(&app_state, id, object_id).await?;
+
+    if object.status != SeatStatus::Available.as_str() {
+        return Err(AppError::Conflict(
+            "Objects that are held or sold cannot be deleted; disable them instead".to_string(),
+        ));
+    }
+    // Deleting would cascade to the reservation items and refunds of past
+    // bookings, so an object keeps its row once it has been booked.
+    let booked = reservation_item::Entity::find()
+        .filter(reservation_item::Column::EventObjectId.eq(object.id))
+        .one(&*app_state.db)
+        .await?;
+    if booked.is_some() {
+        return Err(AppError::Conflict(
+            "Objects that have been booked cannot be deleted; set `is_enable` to false instead"
+                .to_string(),
+        ));
+    }
+
+    let result = event_object::Entity::delete_by_id(object.id)
+        .exec(&*app_state.db)
+        .await?;
+
+    Ok(Json(DeleteResponse {
+        rows_affected: result.rows_affected,
+    }))
+}
+
+async fn find_event_object(
+    app_state: &AppState,
+    event_id: Uuid,
+    object_id: Uuid,
+) -> Result<event_object::Model, AppError> {
+    event_object::Entity::find_by_id(object_id)
+        .filter(event_object::Column::EventId.eq(event_id))
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Event object not found".to_string()))
+}
+
+/// Puts the `objects` an insert returned, in the order they were given, into
+/// their `slots`.
+fn fill_slots(
+    saved: &mut [Option<event_object::Model>],
+    slots: Vec<usize>,
+    objects: Vec<event_object::Model>,
+) {
+    for (slot, object) in slots.into_iter().zip(objects) {
+        saved[slot] = Some(object);
+    }
+}
+
+/// Held or sold objects can only be enabled or disabled.
+fn ensure_movable(
+    object: &event_object::Model,
+    changed: &event_object::Model,
+) -> Result<(), AppError> {
+    let moved = object.object_type != changed.object_type
+        || object.section_id != changed.section_id
+        || object.label != changed.label;
+    if moved && object.status != SeatStatus::Available.as_str() {
+        return Err(AppError::Conflict(format!(
+            "Object `{}` is {} and can only be enabled or disabled",
+            object.id, object.status
+        )));
+    }
+    Ok(())
+}
+
+fn validate_object_type(object_type: &str) -> Result<(), AppError> {
+    if object_type.trim().is_empty() {
+        return Err(AppError::Validation(
+            "`object_type` must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn ensure_sections_in_event(
+    db: &impl ConnectionTrait,
+    event_id: Uuid,
+    section_ids: HashSet<Uuid>,
+) -> Result<(), AppError> {
+    if section_ids.is_empty() {
+        return Ok(());
+    }
+
+    let found = section::Entity::find_live()
+        .filter(section::Column::EventId.eq(event_id))
+        .filter(section::Column::Id.is_in(section_ids.iter().copied()))
+        .all(db)
+        .await?;
+    if found.len() != section_ids.len() {
+        return Err(AppError::Validation(
+            "Some sections do not belong to this event".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::access::{Permission, authorize_event};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::booking::{cancel_event_sales, has_unsold_seats};
+use crate::dto::event::{CancelEventResponse, EventResponse};
+use crate::error::AppError;
+use crate::model::event;
+use crate::status::{EventStatus, SeatStatus};
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn event_status_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(publish_event))
+        .routes(routes!(open_event_sales))
+        .routes(routes!(close_event_sales))
+        .routes(routes!(cancel_event))
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/{id}/publish",
+    tag = "event",
+    responses(
+        (status = 200, body = EventResponse),
+        (status = 409, description = "The event is not a draft")
+    )
+)]
+async fn publish_event(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    let event = transition_event(&*app_state.db, &event, EventStatus::Published).await?;
+    Ok(Json(EventResponse::from(event)))
+}
+
+/// Starts selling an event, or resumes sales of one with seats left.
+#[utoipa::path(
+    post,
+    path = "/event/{id}/open",
+    tag = "event",
+    responses(
+        (status = 200, body = EventResponse),
+        (status = 409, description = "The event is not published, closed or sold out, or has no seats left to sell")
+    )
+)]
+async fn open_event_sales(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    let resumed = [EventStatus::Closed, EventStatus::SoldOut]
+        .iter()
+        .any(|status| event.status == status.as_str());
+    if resumed && !has_unsold_seats(&*app_state.db, id).await? {
+        return Err(AppError::Conflict(
+            "Event has no seats left to sell".to_string(),
+        ));
+    }
+    let event = transition_event(&*app_state.db, &event, EventStatus::OnSale).await?;
+    Ok(Json(EventResponse::from(event)))
+}
+
+/// Stops new reservations. Holds already placed can still be confirmed.
+#[utoipa::path(
+    post,
+    path = "/event/{id}/close",
+    tag = "event",
+    responses(
+        (status = 200, body = EventResponse),
+        (status = 409, description = "The event is not on sale or sold out")
+    )
+)]
+async fn close_event_sales(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    let event = transition_event(&*app_state.db, &event, EventStatus::Closed).await?;
+    Ok(Json(EventResponse::from(event)))
+}
+
+/// Cancels the event, releasing holds and flagging paid reservations for refund.
+#[utoipa::path(
+    post,
+    path = "/event/{id}/cancel",
+    tag = "event",
+    responses(
+        (status = 200, body = CancelEventResponse),
+        (status = 409, description = "The event is already cancelled")
+    )
+)]
+async fn cancel_event(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CancelEventResponse>, AppError> {
+    let event = authorize_event(&app_state.db, &user, id, Permission::Manage).await?;
+
+    // The status update locks the event row, so reservations being created
+    // for it wait and then see it cancelled.
+    let txn = app_state.db.begin().await?;
+    let event = transition_event(&txn, &event, EventStatus::Cancelled).await?;
+    let sales = cancel_event_sales(&txn, event.id).await?;
+    txn.commit().await?;
+
+    app_state
+        .live
+        .publish(event.id, SeatStatus::Available, sales.released);
+    Ok(Json(CancelEventResponse {
+        event: EventResponse::from(event),
+        cancelled_holds: sales.cancelled_holds,
+        refunds_pending: sales.refunds_pending,
+    }))
+}
+
+/// Moves `event` to `to` unless another transition got there first.
+async fn transition_event(
+    db: &impl ConnectionTrait,
+    event: &event::Model,
+    to: EventStatus,
+) -> Result<event::Model, AppError> {
+    let from = EventStatus::parse(&event.status)
+        .filter(|from| from.can_become(to))
+        .ok_or_else(|| {
+            AppError::Conflict(format!(
+                "Event is {} and cannot become {}",
+                event.status,
+                to.as_str()
+            ))
+        })?;
+
+    event::Entity::update_many()
+        .col_expr(event::Column::Status, Expr::value(to.as_str()))
+        .filter(event::Column::Id.eq(event.id))
+        .filter(event::Column::Status.eq(from.as_str()))
+        .exec_with_returning(db)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Conflict(format!("Event is no longer {}", from.as_str())))
+}
@@ -1,19 +1,51 @@
+use crate::access::{Permission, authorize_event, authorize_form};
 use crate::app::AppState;
+use crate::auth::AuthUser;
 use crate::dto::form::{FormRequest, FormResponse, UpdateFormRequest};
 use crate::dto::workspace::DeleteResponse;
 use crate::error::AppError;
 use crate::model::form;
+use crate::trash::{SoftDelete, move_to_trash};
 use axum::{
     Json,
     extract::{Path, Query, State},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
 use std::collections::HashMap;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 pub fn form_routes() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(create_form, get_form, update_form, delete_form))
+    OpenApiRouter::new()
+        .routes(routes!(create_form, get_form, update_form, delete_form))
+        .routes(routes!(list_event_forms))
+        .routes(routes!(restore_form))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/forms",
+    tag = "form",
+    responses((status = 200, body = inline(Vec<FormResponse>)))
+)]
+async fn list_event_forms(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<FormResponse>>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::View).await?;
+
+    let forms = form::Entity::find_live()
+        .filter(form::Column::EventId.eq(id))
+        .order_by_asc(form::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        .map(|form| FormResponse { form })
+        .collect();
+    Ok(Json(forms))
 }
 
 #[utoipa::path(
@@ -25,8 +57,10 @@ pub fn form_routes() -> OpenApiRouter<AppState> {
 )]
 async fn create_form(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<FormRequest>,
 ) -> Result<Json<FormResponse>, AppError> {
+    authorize_event(&app_state.db, &user, body.event_id, Permission::Edit).await?;
     let form = form::ActiveModel {
         event_id: Set(body.event_id),
         title: Set(body.title),
@@ -48,12 +82,10 @@ async fn create_form(
 )]
 async fn get_form(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Path(form_id): Path<Uuid>,
 ) -> Result<Json<FormResponse>, AppError> {
-    let form = form::Entity::find_by_id(form_id)
-        .one(&*app_state.db)
-        .await?
-        .ok_or(AppError::NotFound("Form not found".to_string()))?;
+    let form = authorize_form(&app_state.db, &user, form_id, Permission::View).await?;
 
     Ok(Json(FormResponse { form }))
 }
@@ -67,12 +99,11 @@ async fn get_form(
 )]
 async fn update_form(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<UpdateFormRequest>,
 ) -> Result<Json<FormResponse>, AppError> {
-    let mut form = form::Entity::find_by_id(body.id)
-        .one(&*app_state.db)
+    let mut form = authorize_form(&app_state.db, &user, body.id, Permission::Edit)
         .await?
-        .ok_or(AppError::NotFound("Form not found".to_string()))?
         .into_active_model();
 
     if let Some(title) = body.title {
@@ -103,6 +134,7 @@ async fn update_form(
 )]
 async fn delete_form(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<DeleteResponse>, AppError> {
     let form_id: Uuid = params
@@ -112,16 +144,36 @@ async fn delete_form(
         ))?
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid UUID for `form_id`".to_string()))?;
+    authorize_form(&app_state.db, &user, form_id, Permission::Edit).await?;
 
-    let result = form::Entity::delete_by_id(form_id)
-        .exec(&*app_state.db)
-        .await?;
+    let rows_affected = move_to_trash::<form::Entity>(&*app_state.db, form_id).await?;
 
-    if result.rows_affected == 0 {
+    if rows_affected == 0 {
         return Err(AppError::NotFound("Form not found".to_string()));
     }
 
-    Ok(Json(DeleteResponse {
-        rows_affected: result.rows_affected,
-    }))
+    Ok(Json(DeleteResponse { rows_affected }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/form/{form_id}/restore",
+    tag = "form",
+    responses((status = 200, body = FormResponse))
+)]
+async fn restore_form(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(form_id): Path<Uuid>,
+) -> Result<Json<FormResponse>, AppError> {
+    let form = form::Entity::find_deleted_by_id(form_id)
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Form not in trash".to_string()))?;
+    authorize_event(&app_state.db, &user, form.event_id, Permission::Edit).await?;
+
+    let mut form = form.into_active_model();
+    form.deleted_at = Set(None);
+    let form = form.update(&*app_state.db).await?;
+    Ok(Json(FormResponse { form }))
 }
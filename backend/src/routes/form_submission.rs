@@ -0,0 +1,203 @@
+use crate::access::{Permission, authorize_form, authorize_public_event, authorize_reservation};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::form_submission::{FormSubmissionRequest, FormSubmissionResponse};
+use crate::error::AppError;
+use crate::form_schema;
+use crate::model::{event, form, form_submission};
+use crate::trash::SoftDelete;
+use axum::extract::Path;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn form_submission_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_submissions, submit_form))
+        .routes(routes!(export_submissions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/form/{form_id}/submissions",
+    tag = "form_submission",
+    request_body = FormSubmissionRequest,
+    responses(
+        (status = 200, body = FormSubmissionResponse),
+        (status = 400, description = "The answers do not match the form schema"),
+        (status = 403, description = "The event is not public and the user cannot view it")
+    )
+)]
+async fn submit_form(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(form_id): Path<Uuid>,
+    Json(body): Json<FormSubmissionRequest>,
+) -> Result<Json<FormSubmissionResponse>, AppError> {
+    let form = form::Entity::find_live_by_id(form_id)
+        .inner_join(event::Entity)
+        .filter(event::Column::DeletedAt.is_null())
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Form not found".to_string()))?;
+    authorize_public_event(&app_state.db, &user, form.event_id).await?;
+
+    if !body.data.is_object() {
+        return Err(AppError::Validation(
+            "`data` must be an object of answers".to_string(),
+        ));
+    }
+    if let Some(schema) = &form.schema {
+        form_schema::check(schema, &body.data)?;
+    }
+
+    if let Some(reservation_id) = body.reservation_id {
+        let reservation = authorize_reservation(&app_state.db, &user, reservation_id).await?;
+        if reservation.event_id != form.event_id {
+            return Err(AppError::Validation(
+                "The reservation belongs to another event".to_string(),
+            ));
+        }
+    }
+
+    let submission = form_submission::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        form_id: Set(form.id),
+        user_id: Set(user.id().to_string()),
+        reservation_id: Set(body.reservation_id),
+        data: Set(body.data),
+        ..Default::default()
+    }
+    .insert(&*app_state.db)
+    .await?;
+    Ok(Json(FormSubmissionResponse::from(submission)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/form/{form_id}/submissions",
+    tag = "form_submission",
+    responses((status = 200, body = inline(Vec<FormSubmissionResponse>)))
+)]
+async fn get_submissions(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(form_id): Path<Uuid>,
+) -> Result<Json<Vec<FormSubmissionResponse>>, AppError> {
+    authorize_form(&app_state.db, &user, form_id, Permission::View).await?;
+
+    let submissions = find_submissions(&app_state, form_id)
+        .await?
+        .into_iter()
+        .map(FormSubmissionResponse::from)
+        .collect();
+    Ok(Json(submissions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/form/{form_id}/submissions/export",
+    tag = "form_submission",
+    responses((status = 200, description = "Submissions as CSV, one column per form field", content_type = "text/csv", body = String))
+)]
+async fn export_submissions(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(form_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let form = authorize_form(&app_state.db, &user, form_id, Permission::View).await?;
+    let submissions = find_submissions(&app_state, form_id).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"form-{}-submissions.csv\"", form_id),
+            ),
+        ],
+        submissions_csv(&form, &submissions),
+    ))
+}
+
+async fn find_submissions(
+    app_state: &AppState,
+    form_id: Uuid,
+) -> Result<Vec<form_submission::Model>, AppError> {
+    Ok(form_submission::Entity::find()
+        .filter(form_submission::Column::FormId.eq(form_id))
+        .order_by_asc(form_submission::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?)
+}
+
+/// One row per submission, with the schema's fields in alphabetical order.
+fn submissions_csv(form: &form::Model, submissions: &[form_submission::Model]) -> String {
+    let mut fields: Vec<String> = form
+        .schema
+        .as_ref()
+        .and_then(|schema| schema.get("properties"))
+        .and_then(Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default();
+    let extra: BTreeSet<&String> = submissions
+        .iter()
+        .filter_map(|submission| submission.data.as_object())
+        .flat_map(|data| data.keys())
+        .filter(|key| !fields.contains(key))
+        .collect();
+    fields.extend(extra.into_iter().cloned());
+
+    let mut csv = String::new();
+    let header = ["id", "user_id", "reservation_id", "created_at"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(fields.iter().cloned())
+        .map(|cell| csv_cell(&cell))
+        .collect::<Vec<_>>();
+    csv.push_str(&header.join(","));
+    csv.push_str("\r\n");
+
+    for submission in submissions {
+        let mut row = vec![
+            submission.id.to_string(),
+            submission.user_id.clone(),
+            submission
+                .reservation_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            submission.created_at.to_string(),
+        ];
+        row.extend(fields.iter().map(|field| match submission.data.get(field) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(text)) => text.clone(),
+            Some(other) => other.to_string(),
+        }));
+        let row = row.iter().map(|cell| csv_cell(cell)).collect::<Vec<_>>();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Quotes a cell and defuses values a spreadsheet would run as a formula,
+/// leaving signed numbers as they are.
+fn csv_cell(value: &str) -> String {
+    let number = value.parse::<f64>().is_ok_and(f64::is_finite);
+    let value = if value.starts_with(['=', '+', '-', '@']) && !number {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
@@ -0,0 +1,147 @@
+use crate::access::{Permission, authorize_event};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::layout::{LayoutObject, LayoutResponse, PositionResponse, SaveLayoutRequest};
+use crate::error::AppError;
+use crate::model::{event_object, event_object_position, section};
+use crate::trash::SoftDelete;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, TransactionTrait};
+use std::collections::HashSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Keeps the upsert of a layout within the bind parameters of one statement.
+const MAX_LAYOUT_POSITIONS: usize = 5000;
+
+pub fn layout_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(get_layout, save_layout))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/layout",
+    tag = "layout",
+    responses((status = 200, body = LayoutResponse))
+)]
+async fn get_layout(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<LayoutResponse>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::View).await?;
+
+    let sections = section::Entity::find_live()
+        .filter(section::Column::EventId.eq(id))
+        .order_by_asc(section::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+
+    let objects = event_object::Entity::find()
+        .filter(event_object::Column::EventId.eq(id))
+        .find_also_related(event_object_position::Entity)
+        .order_by_asc(event_object::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        // Objects of a deleted section are hidden with it.
+        .filter(|(object, _)| {
+            object
+                .section_id
+                .is_none_or(|section_id| sections.iter().any(|section| section.id == section_id))
+        })
+        .map(|(object, position)| LayoutObject::new(object, position))
+        .collect();
+
+    Ok(Json(LayoutResponse {
+        event_id: id,
+        sections,
+        objects,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/{id}/layout",
+    tag = "layout",
+    request_body = SaveLayoutRequest,
+    responses((status = 200, body = inline(Vec<PositionResponse>)))
+)]
+async fn save_layout(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SaveLayoutRequest>,
+) -> Result<Json<Vec<PositionResponse>>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::Edit).await?;
+    if body.positions.is_empty() {
+        return Ok(Json(vec![]));
+    }
+    if body.positions.len() > MAX_LAYOUT_POSITIONS {
+        return Err(AppError::Validation(format!(
+            "At most {} positions can be saved at once",
+            MAX_LAYOUT_POSITIONS
+        )));
+    }
+
+    let object_ids: HashSet<Uuid> = body
+        .positions
+        .iter()
+        .map(|position| position.event_object_id)
+        .collect();
+    if object_ids.len() != body.positions.len() {
+        return Err(AppError::Validation(
+            "The same object appears more than once".to_string(),
+        ));
+    }
+
+    let txn = app_state.db.begin().await?;
+
+    let found = event_object::Entity::find()
+        .filter(event_object::Column::EventId.eq(id))
+        .filter(event_object::Column::Id.is_in(object_ids.iter().copied()))
+        .all(&txn)
+        .await?;
+    if found.len() != object_ids.len() {
+        return Err(AppError::Validation(
+            "Some objects do not belong to this event".to_string(),
+        ));
+    }
+
+    let positions = body
+        .positions
+        .into_iter()
+        .map(|position| event_object_position::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            event_object_id: Set(position.event_object_id),
+            position_x: Set(position.position_x),
+            position_y: Set(position.position_y),
+            rotation: Set(position.rotation),
+            ..Default::default()
+        });
+    let saved = event_object_position::Entity::insert_many(positions)
+        .on_conflict(
+            OnConflict::column(event_object_position::Column::EventObjectId)
+                .update_columns([
+                    event_object_position::Column::PositionX,
+                    event_object_position::Column::PositionY,
+                    event_object_position::Column::Rotation,
+                ])
+                .value(
+                    event_object_position::Column::UpdatedAt,
+                    Expr::current_timestamp(),
+                )
+                .to_owned(),
+        )
+        .exec_with_returning_many(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(Json(
+        saved.into_iter().map(PositionResponse::from).collect(),
+    ))
+}
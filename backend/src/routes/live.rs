@@ -0,0 +1,64 @@
+use crate::access::authorize_public_event;
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::live::SeatUpdate;
+use crate::error::AppError;
+use crate::live::Subscription;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn live_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(event_live))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/live",
+    tag = "live",
+    responses((
+        status = 101,
+        description = "WebSocket streaming a `SeatUpdate` JSON message per seat status change",
+        body = SeatUpdate
+    ))
+)]
+async fn event_live(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    authorize_public_event(&app_state.db, &user, id).await?;
+
+    // Subscribe before upgrading so nothing published during the handshake
+    // is missed.
+    let updates = app_state.live.subscribe(id);
+    Ok(ws.on_upgrade(move |socket| stream_seat_updates(socket, updates)))
+}
+
+async fn stream_seat_updates(mut socket: WebSocket, mut updates: Subscription) {
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    let Ok(text) = serde_json::to_string(&update) else {
+                        continue;
+                    };
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // A client that fell behind has a stale seat map; closing makes
+                // it reconnect and reload the layout instead of drifting.
+                Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::access::{
+    MEMBER_ACTIVE, MEMBER_PENDING, Permission, WorkspaceRole, authorize_workspace, check_workspace,
+};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::member::{InviteMemberRequest, MemberResponse};
+use crate::dto::workspace::DeleteResponse;
+use crate::error::AppError;
+use crate::model::workspace_member;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn member_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_members, invite_member))
+        .routes(routes!(accept_invite))
+        .routes(routes!(remove_member))
+}
+
+#[utoipa::path(
+    get,
+    path = "/workspace/{workspace_id}/members",
+    tag = "member",
+    responses((status = 200, body = inline(Vec<MemberResponse>)))
+)]
+async fn get_members(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<Vec<MemberResponse>>, AppError> {
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::View).await?;
+
+    let members = workspace_member::Entity::find()
+        .filter(workspace_member::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(workspace_member::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        .map(MemberResponse::from)
+        .collect();
+    Ok(Json(members))
+}
+
+#[utoipa::path(
+    post,
+    path = "/workspace/{workspace_id}/members",
+    tag = "member",
+    request_body = InviteMemberRequest,
+    responses(
+        (status = 200, body = MemberResponse),
+        (status = 409, description = "The email has already been invited")
+    )
+)]
+async fn invite_member(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(workspace_id): Path<Uuid>,
+    Json(body): Json<InviteMemberRequest>,
+) -> Result<Json<MemberResponse>, AppError> {
+    let workspace =
+        authorize_workspace(&app_state.db, &user, workspace_id, Permission::Manage).await?;
+
+    match body.role {
+        WorkspaceRole::Owner => {
+            return Err(AppError::BadRequest(
+                "A workspace has exactly one owner".to_string(),
+            ));
+        }
+        WorkspaceRole::Admin => {
+            check_workspace(&app_state.db, &user, &workspace, Permission::Own).await?;
+        }
+        _ => {}
+    }
+
+    let email = body.email.trim().to_lowercase();
+    if email.is_empty() {
+        return Err(AppError::BadRequest("Missing `email`".to_string()));
+    }
+
+    let existing = workspace_member::Entity::find()
+        .filter(workspace_member::Column::WorkspaceId.eq(workspace_id))
+        .filter(workspace_member::Column::Email.eq(email.as_str()))
+        .one(&*app_state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            "This email has already been invited".to_string(),
+        ));
+    }
+
+    let member = workspace_member::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        workspace_id: Set(workspace_id),
+        email: Set(email),
+        role: Set(body.role.as_str().to_string()),
+        status: Set(MEMBER_PENDING.to_string()),
+        ..Default::default()
+    };
+    let member = member.insert(&*app_state.db).await?;
+    Ok(Json(MemberResponse::from(member)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/workspace/{workspace_id}/members/accept",
+    tag = "member",
+    responses(
+        (status = 200, body = MemberResponse),
+        (status = 403, description = "The caller's email address is not verified")
+    )
+)]
+async fn accept_invite(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<MemberResponse>, AppError> {
+    // Invitations are matched by email, so only a proven address may claim one.
+    if !user.user.email_verified {
+        return Err(AppError::Forbidden);
+    }
+
+    let mut member = workspace_member::Entity::find()
+        .filter(workspace_member::Column::WorkspaceId.eq(workspace_id))
+        .filter(workspace_member::Column::Email.eq(user.user.email.to_lowercase()))
+        .filter(workspace_member::Column::Status.eq(MEMBER_PENDING))
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Invitation not found".to_string()))?
+        .into_active_model();
+
+    member.user_id = Set(Some(user.id().to_string()));
+    member.status = Set(MEMBER_ACTIVE.to_string());
+    let member = member.update(&*app_state.db).await?;
+    Ok(Json(MemberResponse::from(member)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/workspace/{workspace_id}/members/{member_id}",
+    tag = "member",
+    responses((status = 200, body = DeleteResponse))
+)]
+async fn remove_member(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path((workspace_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    let member = workspace_member::Entity::find_by_id(member_id)
+        .filter(workspace_member::Column::WorkspaceId.eq(workspace_id))
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Member not found".to_string()))?;
+
+    // Members may always leave on their own; removing someone else needs
+    // the right to manage the workspace, and only the owner removes admins.
+    if member.user_id.as_deref() != Some(user.id()) {
+        let workspace =
+            authorize_workspace(&app_state.db, &user, workspace_id, Permission::Manage).await?;
+        if member.role == WorkspaceRole::Admin.as_str() {
+            check_workspace(&app_state.db, &user, &workspace, Permission::Own).await?;
+        }
+    }
+
+    let result = workspace_member::Entity::delete_by_id(member_id)
+        .exec(&*app_state.db)
+        .await?;
+
+    Ok(Json(DeleteResponse {
+        rows_affected: result.rows_affected,
+    }))
+}
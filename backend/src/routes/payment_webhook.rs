@@ -0,0 +1,288 @@
+use crate::app::AppState;
+use crate::booking::{booked_seat_ids, mark_sold_out, reservation_items, transition_seats};
+use crate::dto::payment::PaymentWebhookResponse;
+use crate::error::AppError;
+use crate::model::{payment_event, refund, reservation};
+use crate::payment::{PaymentOutcome, PaymentProvider, WEBHOOK_SIGNATURE_HEADER, WebhookEvent};
+use crate::refund::refund_capture;
+use crate::status::{ReservationStatus, SeatStatus};
+use crate::ticket::{TicketSigner, issue_tickets};
+use axum::body::Bytes;
+use axum::extract::Path;
+use axum::http::HeaderMap;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, IntoActiveModel, QueryFilter,
+    QuerySelect, TransactionTrait,
+};
+use tracing::{Instrument, Span, field, info, info_span, warn};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn payment_webhook_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(receive_payment_webhook))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/payments/{provider}",
+    tag = "payment",
+    security(()),
+    request_body(content = String, content_type = "application/json", description = "Event as sent by the provider"),
+    responses(
+        (status = 200, body = PaymentWebhookResponse),
+        (status = 400, description = "The event cannot be read"),
+        (status = 401, description = "The signature does not match the body"),
+        (status = 404, description = "The provider is not the one payments are taken with"),
+        (status = 409, description = "The seats of the paid reservation are no longer held")
+    )
+)]
+async fn receive_payment_webhook(
+    State(app_state): State<AppState>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<PaymentWebhookResponse>, AppError> {
+    if provider != app_state.payments.name() {
+        return Err(AppError::NotFound(format!(
+            "Payment provider `{}` not found",
+            provider
+        )));
+    }
+    let signature = headers
+        .get(WEBHOOK_SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(AppError::Unauthorized)?;
+    app_state.payments.verify_webhook(&body, signature)?;
+    let event = app_state.payments.parse_webhook(&body)?;
+
+    let span = info_span!(
+        "payment.webhook",
+        provider = %provider,
+        event.id = %event.id,
+        event.r#type = %event.event_type,
+        reservation.id = field::Empty,
+        duplicate = field::Empty,
+    );
+    process_event(&app_state, event).instrument(span).await
+}
+
+/// Stores `event` and applies it to its reservation in one transaction.
+async fn process_event(
+    app_state: &AppState,
+    event: WebhookEvent,
+) -> Result<Json<PaymentWebhookResponse>, AppError> {
+    let provider = app_state.payments.name();
+    let txn = app_state.db.begin().await?;
+    // Locked so a redelivery, a confirm or the expiry sweep of the same
+    // reservation waits for this event to be applied.
+    let reservation = match (&event.reservation_id, &event.intent_id) {
+        (Some(id), _) => {
+            reservation::Entity::find_by_id(*id)
+                .lock_exclusive()
+                .one(&txn)
+                .await?
+        }
+        (None, Some(intent_id)) => {
+            reservation::Entity::find()
+                .filter(reservation::Column::PaymentProvider.eq(provider))
+                .filter(reservation::Column::PaymentIntentId.eq(intent_id))
+                .lock_exclusive()
+                .one(&txn)
+                .await?
+        }
+        (None, None) => None,
+    };
+    if let Some(reservation) = &reservation {
+        Span::current().record("reservation.id", field::display(reservation.id));
+    }
+
+    let stored = payment_event::Entity::insert(payment_event::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        provider: Set(provider.to_string()),
+        provider_event_id: Set(event.id.clone()),
+        event_type: Set(event.event_type.clone()),
+        payload: Set(event.payload.clone()),
+        reservation_id: Set(reservation.as_ref().map(|reservation| reservation.id)),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([
+            payment_event::Column::Provider,
+            payment_event::Column::ProviderEventId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+    let duplicate = stored == 0;
+    Span::current().record("duplicate", duplicate);
+
+    let mut released = None;
+    let mut refunded = false;
+    let reservation = match (reservation, event.outcome) {
+        (Some(reservation), Some(outcome))
+            if !duplicate && reservation.status == ReservationStatus::Pending.as_str() =>
+        {
+            let outcome = if pays_for(&reservation, &event) {
+                Some(outcome)
+            } else if outcome == PaymentOutcome::Succeeded {
+                warn!(
+                    "Payment event {} does not match the intent or total of reservation {}",
+                    event.id, reservation.id
+                );
+                refunded = refund_payment(&txn, &*app_state.payments, &reservation, &event).await?;
+                // A wrong amount on the hold's own intent fails the hold, so a
+                // confirmation racing this event cannot sell its seats.
+                (event.intent_id == reservation.payment_intent_id).then_some(PaymentOutcome::Failed)
+            } else {
+                None
+            };
+            match outcome {
+                Some(outcome) => {
+                    let (reservation, seats) =
+                        settle_reservation(&txn, &app_state.tickets, reservation, outcome).await?;
+                    released = Some(seats);
+                    Some(reservation)
+                }
+                None => Some(reservation),
+            }
+        }
+        (Some(reservation), Some(PaymentOutcome::Succeeded)) if !duplicate => {
+            refunded =
+                refund_late_payment(&txn, &*app_state.payments, &reservation, &event).await?;
+            Some(reservation)
+        }
+        (reservation, _) => reservation,
+    };
+    txn.commit().await?;
+
+    if duplicate {
+        info!("Skipped payment event {} received before", event.id);
+    }
+    if refunded {
+        info!("Refunded the payment of event {}", event.id);
+    }
+    if let (Some(reservation), Some((status, seat_ids))) = (&reservation, released) {
+        info!(
+            "Reservation {} is {} after payment event {}",
+            reservation.id, reservation.status, event.id
+        );
+        app_state
+            .live
+            .publish(reservation.event_id, status, seat_ids);
+    }
+    Ok(Json(PaymentWebhookResponse {
+        duplicate,
+        reservation_status: reservation.map(|reservation| reservation.status),
+        refunded,
+    }))
+}
+
+/// Confirms a pending `reservation` or fails it, releasing its seats.
+async fn settle_reservation(
+    txn: &DatabaseTransaction,
+    tickets: &TicketSigner,
+    reservation: reservation::Model,
+    outcome: PaymentOutcome,
+) -> Result<(reservation::Model, (SeatStatus, Vec<Uuid>)), AppError> {
+    let items = reservation_items(txn, reservation.id).await?;
+    let seat_ids = booked_seat_ids(&items);
+    let event_id = reservation.event_id;
+    let mut reservation = reservation.into_active_model();
+    reservation.expires_at = Set(None);
+
+    let seat_status = match outcome {
+        PaymentOutcome::Succeeded => {
+            let sold = transition_seats(txn, &seat_ids, SeatStatus::Held, SeatStatus::Sold).await?;
+            if sold != seat_ids.len() as u64 {
+                return Err(AppError::Conflict(
+                    "Some seats are no longer held by this reservation".to_string(),
+                ));
+            }
+            reservation.status = Set(ReservationStatus::Confirmed.as_str().to_string());
+            SeatStatus::Sold
+        }
+        PaymentOutcome::Failed => {
+            transition_seats(txn, &seat_ids, SeatStatus::Held, SeatStatus::Available).await?;
+            reservation.status = Set(ReservationStatus::Failed.as_str().to_string());
+            SeatStatus::Available
+        }
+    };
+    let reservation = reservation.update(txn).await?;
+    if seat_status == SeatStatus::Sold {
+        mark_sold_out(txn, event_id).await?;
+        issue_tickets(txn, tickets, event_id, &items).await?;
+    }
+    Ok((reservation, (seat_status, seat_ids)))
+}
+
+/// Whether `event` is about the recorded intent and, if paid, the whole total.
+fn pays_for(reservation: &reservation::Model, event: &WebhookEvent) -> bool {
+    event.intent_id.is_some()
+        && event.intent_id == reservation.payment_intent_id
+        && (event.outcome != Some(PaymentOutcome::Succeeded)
+            || event.amount == Some(reservation.total_price))
+}
+
+async fn refund_payment(
+    txn: &DatabaseTransaction,
+    payments: &dyn PaymentProvider,
+    reservation: &reservation::Model,
+    event: &WebhookEvent,
+) -> Result<bool, AppError> {
+    match (&event.intent_id, event.amount) {
+        (Some(intent_id), Some(amount)) if !amount.is_zero() => {
+            refund_capture(txn, payments, reservation.id, intent_id, amount).await
+        }
+        _ => {
+            warn!("Payment event {} names no payment to refund", event.id);
+            Ok(false)
+        }
+    }
+}
+
+/// Refunds a payment that arrived after its reservation expired, failed or was cancelled.
+async fn refund_late_payment(
+    txn: &DatabaseTransaction,
+    payments: &dyn PaymentProvider,
+    reservation: &reservation::Model,
+    event: &WebhookEvent,
+) -> Result<bool, AppError> {
+    let unpaid = [
+        ReservationStatus::Expired,
+        ReservationStatus::Cancelled,
+        ReservationStatus::Failed,
+    ]
+    .iter()
+    .any(|status| reservation.status == status.as_str());
+    if !unpaid {
+        return Ok(false);
+    }
+    let refunds = refund::Entity::find()
+        .filter(refund::Column::ReservationId.eq(reservation.id))
+        .one(txn)
+        .await?;
+    if refunds.is_some() {
+        return Ok(false);
+    }
+    let Some(intent_id) = event
+        .intent_id
+        .as_ref()
+        .or(reservation.payment_intent_id.as_ref())
+    else {
+        warn!(
+            "Payment event {} for reservation {} names no intent to refund",
+            event.id, reservation.id
+        );
+        return Ok(false);
+    };
+    let amount = event.amount.unwrap_or(reservation.total_price);
+    if amount.is_zero() {
+        return Ok(false);
+    }
+    refund_capture(txn, payments, reservation.id, intent_id, amount).await
+}
@@ -0,0 +1,189 @@
+use crate::access::{Permission, authorize_workspace};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::promo_code::PromoCodeRequest;
+use crate::dto::workspace::DeleteResponse;
+use crate::error::AppError;
+use crate::model::{event, promo_code, section};
+use crate::money::validate_price;
+use crate::promo::{DiscountKind, MAX_CODE_LENGTH, normalize_code};
+use crate::timezone::to_utc;
+use crate::trash::SoftDelete;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn promo_code_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_promo_codes, create_promo_code))
+        .routes(routes!(delete_promo_code))
+}
+
+#[utoipa::path(
+    get,
+    path = "/workspace/{workspace_id}/promo_codes",
+    tag = "promo_code",
+    responses((status = 200, body = inline(Vec<promo_code::Model>)))
+)]
+async fn list_promo_codes(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<Vec<promo_code::Model>>, AppError> {
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::Edit).await?;
+
+    let codes = promo_code::Entity::find()
+        .filter(promo_code::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(promo_code::Column::Code)
+        .all(&*app_state.db)
+        .await?;
+    Ok(Json(codes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/workspace/{workspace_id}/promo_codes",
+    tag = "promo_code",
+    request_body = PromoCodeRequest,
+    responses(
+        (status = 200, body = promo_code::Model),
+        (status = 409, description = "The workspace already has this code")
+    )
+)]
+async fn create_promo_code(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(workspace_id): Path<Uuid>,
+    Json(body): Json<PromoCodeRequest>,
+) -> Result<Json<promo_code::Model>, AppError> {
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::Edit).await?;
+    let promo = promo_code(&app_state.db, workspace_id, body).await?;
+
+    let existing = promo_code::Entity::find()
+        .filter(promo_code::Column::WorkspaceId.eq(workspace_id))
+        .filter(promo_code::Column::Code.eq(promo.code.as_ref().as_str()))
+        .one(&*app_state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            "This code already exists in the workspace".to_string(),
+        ));
+    }
+
+    Ok(Json(promo.insert(&*app_state.db).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/workspace/{workspace_id}/promo_codes/{promo_code_id}",
+    tag = "promo_code",
+    responses((status = 200, body = DeleteResponse))
+)]
+async fn delete_promo_code(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path((workspace_id, promo_code_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::Edit).await?;
+
+    // Reservations that used the code keep their discount line.
+    let result = promo_code::Entity::delete_many()
+        .filter(promo_code::Column::Id.eq(promo_code_id))
+        .filter(promo_code::Column::WorkspaceId.eq(workspace_id))
+        .exec(&*app_state.db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("Promo code not found".to_string()));
+    }
+
+    Ok(Json(DeleteResponse {
+        rows_affected: result.rows_affected,
+    }))
+}
+
+async fn promo_code(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    body: PromoCodeRequest,
+) -> Result<promo_code::ActiveModel, AppError> {
+    let code = normalize_code(&body.code);
+    if code.is_empty() || code.len() > MAX_CODE_LENGTH {
+        return Err(AppError::Validation(format!(
+            "`code` must be 1 to {} characters",
+            MAX_CODE_LENGTH
+        )));
+    }
+    let amount = validate_price(body.amount)?;
+    if amount.is_zero() {
+        return Err(AppError::Validation(
+            "`amount` must be more than zero".to_string(),
+        ));
+    }
+    if body.kind == DiscountKind::Percent && amount > Decimal::ONE_HUNDRED {
+        return Err(AppError::Validation(
+            "A percentage `amount` cannot be more than 100".to_string(),
+        ));
+    }
+    let limit = |limit: Option<u32>, name: &str| {
+        limit
+            .map(|limit| {
+                i32::try_from(limit)
+                    .ok()
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(|| {
+                        AppError::Validation(format!("`{}` must be a positive number", name))
+                    })
+            })
+            .transpose()
+    };
+    let max_uses = limit(body.max_uses, "max_uses")?;
+    let max_uses_per_user = limit(body.max_uses_per_user, "max_uses_per_user")?;
+    let valid_from = body.valid_from.map(to_utc);
+    let valid_until = body.valid_until.map(to_utc);
+    if let (Some(valid_from), Some(valid_until)) = (valid_from, valid_until)
+        && valid_until <= valid_from
+    {
+        return Err(AppError::Validation(
+            "`valid_until` must be after `valid_from`".to_string(),
+        ));
+    }
+
+    if let Some(event_id) = body.event_id {
+        event::Entity::find_live_by_id(event_id)
+            .filter(event::Column::WorkspaceId.eq(workspace_id))
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound("Event not found".to_string()))?;
+    }
+    if let Some(section_id) = body.section_id {
+        let event_id = body.event_id.ok_or(AppError::Validation(
+            "A code limited to a section needs `event_id`".to_string(),
+        ))?;
+        section::Entity::find_live_by_id(section_id)
+            .filter(section::Column::EventId.eq(event_id))
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound("Section not found".to_string()))?;
+    }
+
+    Ok(promo_code::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        workspace_id: Set(workspace_id),
+        event_id: Set(body.event_id),
+        section_id: Set(body.section_id),
+        code: Set(code),
+        kind: Set(body.kind.as_str().to_string()),
+        amount: Set(amount),
+        max_uses: Set(max_uses),
+        max_uses_per_user: Set(max_uses_per_user),
+        valid_from: Set(valid_from),
+        valid_until: Set(valid_until),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,723 @@
+use crate::access::authorize_reservation;
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::booking::{
+    booked_seat_ids, hold_seats, mark_sold_out, reopen_sold_out, reservation_discount,
+    reservation_items, transition_seats,
+};
+use crate::dto::reservation::{
+    CancelItemsRequest, RefundResponse, ReservationRequest, ReservationResponse,
+};
+use crate::error::AppError;
+use crate::model::{
+    check_in, event, event_object, refund, reservation, reservation_discount, reservation_item,
+    section, ticket,
+};
+use crate::payment::{PaymentIntent, PaymentProvider};
+use crate::promo::{redeem_promo_code, remaining_discount};
+use crate::refund::{RefundPolicy, refund_capture};
+use crate::sale_window::{booking_price, sale_windows};
+use crate::status::{EventStatus, ReservationStatus, SeatStatus};
+use crate::ticket::issue_tickets;
+use crate::trash::SoftDelete;
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use chrono::{Duration, Utc};
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+use std::collections::HashSet;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+const HOLD_DURATION_MINUTES: i64 = 10;
+
+pub fn reservation_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(create_reservation))
+        .routes(routes!(get_reservation))
+        .routes(routes!(confirm_reservation))
+        .routes(routes!(cancel_reservation))
+        .routes(routes!(cancel_reservation_items))
+        .routes(routes!(list_refunds))
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservation",
+    tag = "reservation",
+    request_body = ReservationRequest,
+    responses(
+        (status = 200, body = ReservationResponse),
+        (status = 403, description = "The open sale window needs an access code"),
+        (status = 409, description = "Some seats are no longer available or not on sale, or the promo code is used up")
+    )
+)]
+async fn create_reservation(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<ReservationRequest>,
+) -> Result<Json<ReservationResponse>, AppError> {
+    if body.event_object_ids.is_empty() {
+        return Err(AppError::Validation(
+            "At least one seat must be selected".to_string(),
+        ));
+    }
+    if body.event_object_ids.iter().collect::<HashSet<_>>().len() != body.event_object_ids.len() {
+        return Err(AppError::Validation(
+            "The same seat appears more than once".to_string(),
+        ));
+    }
+
+    let txn = app_state.db.begin().await?;
+    // `FOR SHARE` makes a concurrent cancel wait until this hold is in place,
+    // so it is released along with the others.
+    let event = event::Entity::find_live_by_id(body.event_id)
+        .lock_shared()
+        .one(&txn)
+        .await?
+        .ok_or(AppError::NotFound("Event not found".to_string()))?;
+    if event.status != EventStatus::OnSale.as_str() {
+        return Err(AppError::Conflict(format!("Event is {}", event.status)));
+    }
+    hold_seats(&txn, event.id, &body.event_object_ids).await?;
+
+    let seats = event_object::Entity::find()
+        .filter(event_object::Column::Id.is_in(body.event_object_ids.clone()))
+        .find_also_related(section::Entity)
+        .all(&txn)
+        .await?;
+    // Seats of a deleted section are off sale until it is restored; returning
+    // early rolls the hold back.
+    if seats.iter().any(|(_, section)| {
+        section
+            .as_ref()
+            .is_some_and(|section| section.deleted_at.is_some())
+    }) {
+        return Err(AppError::Conflict(
+            "Some seats are no longer available".to_string(),
+        ));
+    }
+
+    // Each seat is priced by the sale window of its section open right now.
+    let now = Utc::now().naive_utc();
+    let windows = sale_windows(
+        &txn,
+        seats
+            .iter()
+            .filter_map(|(_, section)| section.as_ref().map(|section| section.id))
+            .collect::<HashSet<_>>(),
+    )
+    .await?;
+    let prices = seats
+        .iter()
+        .map(|(seat, section)| match section {
+            Some(section) => booking_price(
+                section,
+                windows.get(&section.id).map_or(&[], Vec::as_slice),
+                now,
+                body.access_code.as_deref(),
+            ),
+            None => Err(AppError::Conflict(format!(
+                "Seat {} has no section to price it",
+                seat.label.as_deref().unwrap_or("without a label")
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let reservation_id = Uuid::new_v4();
+    let items: Vec<reservation_item::ActiveModel> = seats
+        .iter()
+        .zip(&prices)
+        .map(|((seat, _), price)| reservation_item::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            reservation_id: Set(reservation_id),
+            event_object_id: Set(seat.id),
+            price_at_booking: Set(*price),
+            ..Default::default()
+        })
+        .collect();
+    let subtotal: Decimal = prices.iter().sum();
+    let promo = match body.promo_code.as_deref() {
+        Some(code) => {
+            let lines: Vec<_> = seats
+                .iter()
+                .zip(&prices)
+                .map(|((seat, _), price)| (seat.section_id, *price))
+                .collect();
+            Some(redeem_promo_code(&txn, &event, user.id(), code, &lines, now).await?)
+        }
+        None => None,
+    };
+    let total_price = subtotal - promo.as_ref().map_or(Decimal::ZERO, |(_, amount)| *amount);
+
+    let reservation = reservation::ActiveModel {
+        id: Set(reservation_id),
+        user_id: Set(user.id().to_string()),
+        event_id: Set(event.id),
+        status: Set(ReservationStatus::Pending.as_str().to_string()),
+        total_price: Set(total_price),
+        currency: Set(event.currency.clone()),
+        expires_at: Set(Some(now + Duration::minutes(HOLD_DURATION_MINUTES))),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    let items = reservation_item::Entity::insert_many(items)
+        .exec_with_returning_many(&txn)
+        .await?;
+    let discount = match promo {
+        Some((promo, amount)) => Some(
+            reservation_discount::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                reservation_id: Set(reservation.id),
+                promo_code_id: Set(Some(promo.id)),
+                code: Set(promo.code),
+                amount: Set(amount),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?,
+        ),
+        None => None,
+    };
+
+    txn.commit().await?;
+    app_state
+        .live
+        .publish(event.id, SeatStatus::Held, body.event_object_ids);
+    Ok(Json(ReservationResponse::new(reservation, items, discount)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reservation/{id}",
+    tag = "reservation",
+    responses((status = 200, body = ReservationResponse))
+)]
+async fn get_reservation(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationResponse>, AppError> {
+    let reservation = authorize_reservation(&app_state.db, &user, id).await?;
+    let items = reservation_items(&*app_state.db, id).await?;
+    let discount = reservation_discount(&*app_state.db, id).await?;
+    Ok(Json(ReservationResponse::new(reservation, items, discount)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservation/{id}/confirm",
+    tag = "reservation",
+    responses(
+        (status = 200, body = ReservationResponse),
+        (status = 402, description = "The payment was declined"),
+        (status = 409, description = "The hold is no longer pending or has expired")
+    )
+)]
+async fn confirm_reservation(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationResponse>, AppError> {
+    let reservation = authorize_reservation(&app_state.db, &user, id).await?;
+    ensure_pending(&reservation)?;
+    ensure_unexpired(&reservation)?;
+
+    // The intent is recorded before capture so the webhook can settle it.
+    let intent = if reservation.total_price.is_zero() {
+        None
+    } else {
+        let intent = app_state
+            .payments
+            .create_intent(
+                reservation.id,
+                reservation.total_price,
+                &reservation.currency,
+            )
+            .await?;
+        let recorded = reservation::Entity::update_many()
+            .col_expr(
+                reservation::Column::PaymentProvider,
+                Expr::value(app_state.payments.name()),
+            )
+            .col_expr(
+                reservation::Column::PaymentIntentId,
+                Expr::value(intent.id.clone()),
+            )
+            .filter(reservation::Column::Id.eq(id))
+            .filter(reservation::Column::Status.eq(ReservationStatus::Pending.as_str()))
+            .filter(reservation::Column::TotalPrice.eq(intent.amount))
+            .exec(&*app_state.db)
+            .await?;
+        if recorded.rows_affected == 0 {
+            return Err(AppError::Conflict(
+                "The hold changed while it was being confirmed".to_string(),
+            ));
+        }
+        // A declined payment leaves the seats held until the hold runs out.
+        app_state.payments.capture(&intent.id).await?;
+        Some(intent)
+    };
+
+    let confirmed = settle_confirmation(&app_state, id, intent.as_ref()).await;
+    let (reservation, items, discount, seat_ids) = match (confirmed, intent) {
+        (Ok(confirmed), _) => confirmed,
+        (Err(err), Some(intent)) => {
+            if let Err(refund_err) = refund_failed_confirmation(&app_state, id, &intent).await {
+                tracing::error!(
+                    reservation.id = %id,
+                    payment.intent = %intent.id,
+                    "Failed to refund a capture whose confirmation failed: {}",
+                    refund_err
+                );
+            }
+            return Err(err);
+        }
+        (Err(err), None) => return Err(err),
+    };
+    app_state
+        .live
+        .publish(reservation.event_id, SeatStatus::Sold, seat_ids);
+    Ok(Json(ReservationResponse::new(reservation, items, discount)))
+}
+
+async fn refund_failed_confirmation(
+    app_state: &AppState,
+    id: Uuid,
+    intent: &PaymentIntent,
+) -> Result<(), AppError> {
+    let txn = app_state.db.begin().await?;
+    let reservation = lock_reservation(&txn, id).await?;
+    // Another confirm or the webhook may have sold the seats with this capture.
+    let settled = reservation.status == ReservationStatus::Confirmed.as_str()
+        && reservation.payment_intent_id.as_deref() == Some(intent.id.as_str());
+    if !settled {
+        refund_capture(&txn, &*app_state.payments, id, &intent.id, intent.amount).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Sells the seats of pending reservation `id` once `intent`, if any, is captured.
+async fn settle_confirmation(
+    app_state: &AppState,
+    id: Uuid,
+    intent: Option<&PaymentIntent>,
+) -> Result<
+    (
+        reservation::Model,
+        Vec<reservation_item::Model>,
+        Option<reservation_discount::Model>,
+        Vec<Uuid>,
+    ),
+    AppError,
+> {
+    let txn = app_state.db.begin().await?;
+    let reservation = lock_pending_reservation(&txn, id).await?;
+    match intent {
+        // A hold that ran out while the payment went through is still
+        // honoured as long as its seats have not been released.
+        Some(intent) => {
+            if reservation.total_price != intent.amount {
+                return Err(AppError::Conflict(
+                    "The hold changed while it was being confirmed".to_string(),
+                ));
+            }
+        }
+        None => ensure_unexpired(&reservation)?,
+    }
+
+    let items = reservation_items(&txn, id).await?;
+    let discount = reservation_discount(&txn, id).await?;
+    let seat_ids = booked_seat_ids(&items);
+    let sold = transition_seats(&txn, &seat_ids, SeatStatus::Held, SeatStatus::Sold).await?;
+    if sold != seat_ids.len() as u64 {
+        return Err(AppError::Conflict(
+            "Some seats are no longer held by this reservation".to_string(),
+        ));
+    }
+
+    let mut reservation = reservation.into_active_model();
+    reservation.status = Set(ReservationStatus::Confirmed.as_str().to_string());
+    reservation.expires_at = Set(None);
+    let reservation = reservation.update(&txn).await?;
+    mark_sold_out(&txn, reservation.event_id).await?;
+    issue_tickets(&txn, &app_state.tickets, reservation.event_id, &items).await?;
+    txn.commit().await?;
+    Ok((reservation, items, discount, seat_ids))
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservation/{id}/cancel",
+    tag = "reservation",
+    responses(
+        (status = 200, body = ReservationResponse),
+        (status = 409, description = "The reservation is already cancelled, expired or failed, a seat was checked in or the event has started")
+    )
+)]
+async fn cancel_reservation(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationResponse>, AppError> {
+    authorize_reservation(&app_state.db, &user, id).await?;
+
+    let txn = app_state.db.begin().await?;
+    let reservation = lock_reservation(&txn, id).await?;
+    let items = reservation_items(&txn, id).await?;
+    let discount = reservation_discount(&txn, id).await?;
+
+    // Paid reservations are cancelled seat by seat so each one is refunded.
+    if is_paid(&reservation) {
+        let cancel = items
+            .iter()
+            .filter(|item| item.cancelled_at.is_none())
+            .map(|item| item.id)
+            .collect();
+        let (reservation, items, discount, seat_ids) = cancel_items(
+            &*app_state.payments,
+            &txn,
+            reservation,
+            items,
+            &cancel,
+            discount,
+        )
+        .await?;
+        txn.commit().await?;
+        app_state
+            .live
+            .publish(reservation.event_id, SeatStatus::Available, seat_ids);
+        return Ok(Json(ReservationResponse::new(reservation, items, discount)));
+    }
+    ensure_pending(&reservation)?;
+
+    let seat_ids = booked_seat_ids(&items);
+    transition_seats(&txn, &seat_ids, SeatStatus::Held, SeatStatus::Available).await?;
+
+    let mut reservation = reservation.into_active_model();
+    reservation.status = Set(ReservationStatus::Cancelled.as_str().to_string());
+    reservation.expires_at = Set(None);
+    let reservation = reservation.update(&txn).await?;
+
+    txn.commit().await?;
+    app_state
+        .live
+        .publish(reservation.event_id, SeatStatus::Available, seat_ids);
+    Ok(Json(ReservationResponse::new(reservation, items, discount)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservation/{id}/items/cancel",
+    tag = "reservation",
+    request_body = CancelItemsRequest,
+    responses(
+        (status = 200, body = ReservationResponse),
+        (status = 400, description = "A seat is not part of the reservation or already cancelled"),
+        (status = 409, description = "The reservation is already cancelled, expired or failed, a seat was checked in or the event has started")
+    )
+)]
+async fn cancel_reservation_items(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CancelItemsRequest>,
+) -> Result<Json<ReservationResponse>, AppError> {
+    if body.reservation_item_ids.is_empty() {
+        return Err(AppError::Validation(
+            "At least one seat must be selected".to_string(),
+        ));
+    }
+    let cancel: HashSet<Uuid> = body.reservation_item_ids.iter().copied().collect();
+    if cancel.len() != body.reservation_item_ids.len() {
+        return Err(AppError::Validation(
+            "The same seat appears more than once".to_string(),
+        ));
+    }
+    authorize_reservation(&app_state.db, &user, id).await?;
+
+    let txn = app_state.db.begin().await?;
+    let reservation = lock_reservation(&txn, id).await?;
+    if !is_paid(&reservation) {
+        ensure_pending(&reservation)?;
+    }
+    let items = reservation_items(&txn, id).await?;
+    for item_id in &cancel {
+        match items.iter().find(|item| item.id == *item_id) {
+            Some(item) if item.cancelled_at.is_none() => {}
+            Some(_) => {
+                return Err(AppError::Validation(format!(
+                    "Seat `{}` is already cancelled",
+                    item_id
+                )));
+            }
+            None => {
+                return Err(AppError::Validation(format!(
+                    "Seat `{}` is not part of this reservation",
+                    item_id
+                )));
+            }
+        }
+    }
+    let discount = reservation_discount(&txn, id).await?;
+
+    let (reservation, items, discount, seat_ids) = cancel_items(
+        &*app_state.payments,
+        &txn,
+        reservation,
+        items,
+        &cancel,
+        discount,
+    )
+    .await?;
+    txn.commit().await?;
+    app_state
+        .live
+        .publish(reservation.event_id, SeatStatus::Available, seat_ids);
+    Ok(Json(ReservationResponse::new(reservation, items, discount)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reservation/{id}/refunds",
+    tag = "reservation",
+    responses((status = 200, body = Vec<RefundResponse>))
+)]
+async fn list_refunds(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<RefundResponse>>, AppError> {
+    authorize_reservation(&app_state.db, &user, id).await?;
+    let refunds = refund::Entity::find()
+        .filter(refund::Column::ReservationId.eq(id))
+        .order_by_asc(refund::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+    Ok(Json(
+        refunds.into_iter().map(RefundResponse::from).collect(),
+    ))
+}
+
+/// Cancels the `cancel` items of a locked `reservation`, refunding paid seats.
+async fn cancel_items(
+    payments: &dyn PaymentProvider,
+    txn: &DatabaseTransaction,
+    reservation: reservation::Model,
+    items: Vec<reservation_item::Model>,
+    cancel: &HashSet<Uuid>,
+    discount: Option<reservation_discount::Model>,
+) -> Result<
+    (
+        reservation::Model,
+        Vec<reservation_item::Model>,
+        Option<reservation_discount::Model>,
+        Vec<Uuid>,
+    ),
+    AppError,
+> {
+    let paid = is_paid(&reservation);
+    let event_cancelled = reservation.status == ReservationStatus::RefundPending.as_str();
+    let now = Utc::now().naive_utc();
+    let (cancelled, kept): (Vec<_>, Vec<_>) = items
+        .iter()
+        .filter(|item| item.cancelled_at.is_none())
+        .partition(|item| cancel.contains(&item.id));
+
+    let cancelled_ids: Vec<Uuid> = cancelled.iter().map(|item| item.id).collect();
+    let policy = if paid {
+        let event = event::Entity::find_by_id(reservation.event_id)
+            .one(txn)
+            .await?
+            .ok_or(AppError::NotFound("Event not found".to_string()))?;
+        if !event_cancelled && event.starts_at.is_some_and(|starts_at| starts_at <= now) {
+            return Err(AppError::Conflict(
+                "Event has already started and its seats can no longer be cancelled".to_string(),
+            ));
+        }
+        let checked_in = check_in::Entity::find()
+            .inner_join(ticket::Entity)
+            .filter(ticket::Column::ReservationItemId.is_in(cancelled_ids.clone()))
+            .filter(check_in::Column::UndoneAt.is_null())
+            .one(txn)
+            .await?;
+        if checked_in.is_some() {
+            return Err(AppError::Conflict(
+                "A seat has already been checked in and can no longer be cancelled".to_string(),
+            ));
+        }
+        if event_cancelled {
+            Some(RefundPolicy::default())
+        } else {
+            Some(RefundPolicy::from_settings(event.settings.as_ref())?)
+        }
+    } else {
+        None
+    };
+
+    let seat_ids: Vec<Uuid> = cancelled.iter().map(|item| item.event_object_id).collect();
+    let from = if paid {
+        SeatStatus::Sold
+    } else {
+        SeatStatus::Held
+    };
+    let released = transition_seats(txn, &seat_ids, from, SeatStatus::Available).await?;
+    if released != seat_ids.len() as u64 {
+        return Err(AppError::Conflict(
+            "Some seats are no longer held by this reservation".to_string(),
+        ));
+    }
+    reservation_item::Entity::update_many()
+        .col_expr(reservation_item::Column::CancelledAt, Expr::value(now))
+        .filter(reservation_item::Column::Id.is_in(cancelled_ids.clone()))
+        .exec(txn)
+        .await?;
+    if paid {
+        // Tickets of cancelled seats stop admitting anyone but are kept, along
+        // with their check-in history.
+        ticket::Entity::update_many()
+            .col_expr(ticket::Column::RevokedAt, Expr::value(now))
+            .filter(ticket::Column::ReservationItemId.is_in(cancelled_ids))
+            .filter(ticket::Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+    }
+
+    // The promo code applies again to the seats left, as if they had been
+    // booked alone.
+    let discount = match discount {
+        Some(discount) => {
+            let amount = remaining_discount(txn, &discount, &kept).await?;
+            if amount == discount.amount {
+                Some(discount)
+            } else {
+                let mut discount = discount.into_active_model();
+                discount.amount = Set(amount);
+                Some(discount.update(txn).await?)
+            }
+        }
+        None => None,
+    };
+    let subtotal: Decimal = kept.iter().map(|item| item.price_at_booking).sum();
+    let total_price = subtotal
+        - discount
+            .as_ref()
+            .map_or(Decimal::ZERO, |discount| discount.amount);
+
+    let mut refunds = Vec::new();
+    let mut refunded = Decimal::ZERO;
+    if let Some(policy) = policy {
+        // Never refund more than was paid for the cancelled seats.
+        let mut refundable = reservation.total_price - total_price;
+        for item in &cancelled {
+            let (amount, fee) = policy.split(item.price_at_booking);
+            let paid = amount.min(refundable);
+            refundable -= paid;
+            refunded += paid;
+            refunds.push(refund::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                reservation_id: Set(reservation.id),
+                reservation_item_id: Set(item.id),
+                amount: Set(paid),
+                fee: Set(fee),
+                discount: Set(amount - paid),
+                ..Default::default()
+            });
+        }
+    }
+
+    let event_id = reservation.event_id;
+    let payment_intent_id = reservation.payment_intent_id.clone();
+    let mut reservation = reservation.into_active_model();
+    reservation.total_price = Set(total_price);
+    if kept.is_empty() {
+        reservation.status = Set(ReservationStatus::Cancelled.as_str().to_string());
+        reservation.expires_at = Set(None);
+    }
+    let reservation = reservation.update(txn).await?;
+
+    if paid {
+        reopen_sold_out(txn, event_id).await?;
+        // Refunded last so a provider error rolls the cancellation back and
+        // the seats stay sold.
+        let payment_refund_id = match payment_intent_id {
+            Some(intent_id) if !refunded.is_zero() => {
+                Some(payments.refund(&intent_id, refunded).await?.id)
+            }
+            _ => None,
+        };
+        for refund in &mut refunds {
+            refund.payment_refund_id = Set(payment_refund_id.clone());
+        }
+        refund::Entity::insert_many(refunds)
+            .exec_without_returning(txn)
+            .await?;
+    }
+
+    let items = items
+        .into_iter()
+        .map(|item| {
+            if cancel.contains(&item.id) && item.cancelled_at.is_none() {
+                reservation_item::Model {
+                    cancelled_at: Some(now),
+                    ..item
+                }
+            } else {
+                item
+            }
+        })
+        .collect();
+    Ok((reservation, items, discount, seat_ids))
+}
+
+fn is_paid(reservation: &reservation::Model) -> bool {
+    reservation.status == ReservationStatus::Confirmed.as_str()
+        || reservation.status == ReservationStatus::RefundPending.as_str()
+}
+
+async fn lock_reservation(
+    txn: &DatabaseTransaction,
+    id: Uuid,
+) -> Result<reservation::Model, AppError> {
+    reservation::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or(AppError::NotFound("Reservation not found".to_string()))
+}
+
+async fn lock_pending_reservation(
+    txn: &DatabaseTransaction,
+    id: Uuid,
+) -> Result<reservation::Model, AppError> {
+    let reservation = lock_reservation(txn, id).await?;
+    ensure_pending(&reservation)?;
+    Ok(reservation)
+}
+
+fn ensure_unexpired(reservation: &reservation::Model) -> Result<(), AppError> {
+    if reservation
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now().naive_utc())
+    {
+        return Err(AppError::Conflict("The hold has expired".to_string()));
+    }
+    Ok(())
+}
+
+fn ensure_pending(reservation: &reservation::Model) -> Result<(), AppError> {
+    if reservation.status != ReservationStatus::Pending.as_str() {
+        return Err(AppError::Conflict(format!(
+            "Reservation is {}",
+            reservation.status
+        )));
+    }
+    Ok(())
+}
@@ -1,25 +1,72 @@
 use std::collections::HashMap;
 
+use crate::access::{Permission, authorize_event, authorize_section};
 use crate::app::AppState;
-use crate::dto::section::{SectionRequest, SectionResponse, UpdateSectionRequest};
+use crate::auth::AuthUser;
+use crate::booking::section_seat_counts;
+use crate::dto::section::{
+    SaleWindowRequest, SectionRequest, SectionResponse, SectionSeatsResponse, UpdateSectionRequest,
+};
 use crate::dto::workspace::DeleteResponse;
 use crate::error::AppError;
-use crate::model::section;
+use crate::model::{section, section_sale_window};
+use crate::money::validate_price;
+use crate::sale_window::sale_windows;
+use crate::timezone::to_utc;
+use crate::trash::{SoftDelete, move_to_trash};
 use axum::extract::Query;
 use axum::{
     Json,
     extract::{Path, State},
 };
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 pub fn section_routes() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(
-        get_section,
-        create_section,
-        delete_section,
-        update_section
+    OpenApiRouter::new()
+        .routes(routes!(
+            get_section,
+            create_section,
+            delete_section,
+            update_section
+        ))
+        .routes(routes!(list_event_sections))
+        .routes(routes!(restore_section))
+        .routes(routes!(get_sale_windows, replace_sale_windows))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/{id}/sections",
+    tag = "section",
+    responses((status = 200, body = inline(Vec<SectionSeatsResponse>)))
+)]
+async fn list_event_sections(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<SectionSeatsResponse>>, AppError> {
+    authorize_event(&app_state.db, &user, id, Permission::View).await?;
+
+    let sections = section::Entity::find_live()
+        .filter(section::Column::EventId.eq(id))
+        .order_by_asc(section::Column::CreatedAt)
+        .all(&*app_state.db)
+        .await?;
+    let counts = section_seat_counts(&*app_state.db, id).await?;
+
+    Ok(Json(
+        sections
+            .into_iter()
+            .map(|section| SectionSeatsResponse {
+                seats: counts.get(&section.id).copied().unwrap_or_default(),
+                section,
+            })
+            .collect(),
     ))
 }
 
@@ -31,12 +78,10 @@ pub fn section_routes() -> OpenApiRouter<AppState> {
 )]
 pub async fn get_section(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<Json<SectionResponse>, AppError> {
-    let section = section::Entity::find_by_id(id)
-        .one(&*app_state.db)
-        .await?
-        .ok_or(AppError::NotFound("Section not found".to_string()))?;
+    let section = authorize_section(&app_state.db, &user, id, Permission::View).await?;
     Ok(Json(SectionResponse { section }))
 }
 
@@ -49,12 +94,14 @@ pub async fn get_section(
 )]
 async fn create_section(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<SectionRequest>,
 ) -> Result<Json<SectionResponse>, AppError> {
+    authorize_event(&app_state.db, &user, body.event_id, Permission::Edit).await?;
     let section = section::ActiveModel {
         event_id: Set(body.event_id),
         title: Set(body.title),
-        price: Set(body.price),
+        price: Set(validate_price(body.price)?),
         ..Default::default()
     };
 
@@ -73,6 +120,7 @@ async fn create_section(
 )]
 async fn delete_section(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<DeleteResponse>, AppError> {
     let section_id: Uuid = params
@@ -82,17 +130,37 @@ async fn delete_section(
         ))?
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid UUID for `id`".to_string()))?;
-    let result = section::Entity::delete_by_id(section_id)
-        .exec(&*app_state.db)
-        .await?;
+    authorize_section(&app_state.db, &user, section_id, Permission::Edit).await?;
+    let rows_affected = move_to_trash::<section::Entity>(&*app_state.db, section_id).await?;
 
-    if result.rows_affected == 0 {
+    if rows_affected == 0 {
         return Err(AppError::NotFound("Section not found".to_string()));
     }
 
-    Ok(Json(DeleteResponse {
-        rows_affected: result.rows_affected,
-    }))
+    Ok(Json(DeleteResponse { rows_affected }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/section/{id}/restore",
+    tag = "section",
+    responses((status = 200, body = SectionResponse))
+)]
+async fn restore_section(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SectionResponse>, AppError> {
+    let section = section::Entity::find_deleted_by_id(id)
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Section not in trash".to_string()))?;
+    authorize_event(&app_state.db, &user, section.event_id, Permission::Edit).await?;
+
+    let mut section = section.into_active_model();
+    section.deleted_at = Set(None);
+    let section = section.update(&*app_state.db).await?;
+    Ok(Json(SectionResponse { section }))
 }
 
 #[utoipa::path(
@@ -104,19 +172,18 @@ async fn delete_section(
 )]
 async fn update_section(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<UpdateSectionRequest>,
 ) -> Result<Json<SectionResponse>, AppError> {
-    let mut section = section::Entity::find_by_id(body.id)
-        .one(&*app_state.db)
+    let mut section = authorize_section(&app_state.db, &user, body.id, Permission::Edit)
         .await?
-        .ok_or(AppError::NotFound("Section not found".to_string()))?
         .into_active_model();
 
     if let Some(title) = body.title {
         section.title = Set(title);
     }
     if let Some(price) = body.price {
-        section.price = Set(price);
+        section.price = Set(validate_price(price)?);
     }
 
     let updated_section = section.update(&*app_state.db).await?;
@@ -124,3 +191,89 @@ async fn update_section(
         section: updated_section,
     }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/section/{id}/windows",
+    tag = "section",
+    responses((status = 200, body = inline(Vec<section_sale_window::Model>)))
+)]
+async fn get_sale_windows(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<section_sale_window::Model>>, AppError> {
+    authorize_section(&app_state.db, &user, id, Permission::View).await?;
+    let windows = sale_windows(&*app_state.db, [id]).await?;
+    Ok(Json(windows.into_values().flatten().collect()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/section/{id}/windows",
+    tag = "section",
+    request_body = Vec<SaleWindowRequest>,
+    responses((status = 200, body = inline(Vec<section_sale_window::Model>)))
+)]
+async fn replace_sale_windows(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<Vec<SaleWindowRequest>>,
+) -> Result<Json<Vec<section_sale_window::Model>>, AppError> {
+    authorize_section(&app_state.db, &user, id, Permission::Edit).await?;
+    let windows = body
+        .into_iter()
+        .map(|window| sale_window(id, window))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let txn = app_state.db.begin().await?;
+    section_sale_window::Entity::delete_many()
+        .filter(section_sale_window::Column::SectionId.eq(id))
+        .exec(&txn)
+        .await?;
+    let windows = if windows.is_empty() {
+        Vec::new()
+    } else {
+        section_sale_window::Entity::insert_many(windows)
+            .exec_with_returning_many(&txn)
+            .await?
+    };
+    txn.commit().await?;
+    Ok(Json(windows))
+}
+
+fn sale_window(
+    section_id: Uuid,
+    window: SaleWindowRequest,
+) -> Result<section_sale_window::ActiveModel, AppError> {
+    let name = window.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Every sale window needs a `name`".to_string(),
+        ));
+    }
+    let opens_at = window.opens_at.map(to_utc);
+    let closes_at = window.closes_at.map(to_utc);
+    if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at)
+        && closes_at <= opens_at
+    {
+        return Err(AppError::Validation(format!(
+            "Sale window `{}` must close after it opens",
+            name
+        )));
+    }
+    Ok(section_sale_window::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        section_id: Set(section_id),
+        name: Set(name.to_string()),
+        price: Set(validate_price(window.price)?),
+        opens_at: Set(opens_at),
+        closes_at: Set(closes_at),
+        access_code: Set(window
+            .access_code
+            .map(|code| code.trim().to_string())
+            .filter(|code| !code.is_empty())),
+        ..Default::default()
+    })
+}
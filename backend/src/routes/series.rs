@@ -0,0 +1,233 @@
+use crate::access::{Permission, authorize_event, authorize_series};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::event::EventResponse;
+use crate::dto::series::{OccurrenceQuery, SeriesRequest, SeriesResponse, UpdateSeriesRequest};
+use crate::error::AppError;
+use crate::event_copy::{EventCopy, EventTemplate};
+use crate::model::{event, event_series};
+use crate::recurrence::Recurrence;
+use crate::refund::RefundPolicy;
+use crate::timezone::{event_timezone, local_to_utc, to_utc};
+use crate::trash::SoftDelete;
+use axum::extract::{Path, Query};
+use axum::{Json, extract::State};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use sea_orm::ActiveValue::Set;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Select,
+    TransactionTrait,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn series_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(create_series))
+        .routes(routes!(get_series, update_series))
+        .routes(routes!(list_occurrences))
+}
+
+#[utoipa::path(
+    post,
+    path = "/series",
+    tag = "series",
+    request_body = SeriesRequest,
+    responses((status = 200, body = SeriesResponse))
+)]
+async fn create_series(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<SeriesRequest>,
+) -> Result<Json<SeriesResponse>, AppError> {
+    let template = authorize_event(
+        &app_state.db,
+        &user,
+        body.template_event_id,
+        Permission::Edit,
+    )
+    .await?;
+
+    // Expand the rule in local time so occurrences keep their wall-clock
+    // time across daylight saving changes.
+    let tz = event_timezone(&template.timezone);
+    let local = |time: DateTime<FixedOffset>| tz.from_utc_datetime(&to_utc(time)).naive_local();
+    let recurrence = Recurrence {
+        starts_at: local(body.starts_at),
+        frequency: body.frequency,
+        interval: body.interval.unwrap_or(1),
+        count: body.count,
+        until: body.until.map(local),
+        exclusions: body.exclusions.unwrap_or_default(),
+    };
+    let starts: Vec<_> = recurrence
+        .occurrences()?
+        .into_iter()
+        .map(|starts_at| local_to_utc(tz, starts_at))
+        .collect();
+    if starts.is_empty() {
+        return Err(AppError::Validation(
+            "The series has no occurrences".to_string(),
+        ));
+    }
+    let interval = i32::try_from(recurrence.interval)
+        .map_err(|_| AppError::Validation("`interval` is too large".to_string()))?;
+    let duration = template
+        .starts_at
+        .zip(template.ends_at)
+        .map(|(starts_at, ends_at)| ends_at - starts_at);
+    let title = body.title.unwrap_or_else(|| template.title.clone());
+
+    let txn = app_state.db.begin().await?;
+    let series = event_series::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        workspace_id: Set(template.workspace_id),
+        template_event_id: Set(Some(template.id)),
+        title: Set(title.clone()),
+        starts_at: Set(to_utc(body.starts_at)),
+        frequency: Set(recurrence.frequency.as_str().to_string()),
+        interval: Set(interval),
+        count: Set(recurrence.count.map(|count| count as i32)),
+        until: Set(body.until.map(to_utc)),
+        exclusions: Set(serde_json::json!(recurrence.exclusions)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    let template = EventTemplate::load(&txn, template).await?;
+    let mut occurrences = Vec::with_capacity(starts.len());
+    for starts_at in starts {
+        let occurrence = template
+            .copy(
+                &txn,
+                EventCopy {
+                    workspace_id: series.workspace_id,
+                    title: title.clone(),
+                    starts_at: Some(starts_at),
+                    ends_at: duration.map(|duration| starts_at + duration),
+                    series_id: Some(series.id),
+                },
+            )
+            .await?;
+        occurrences.push(EventResponse::from(occurrence));
+    }
+    txn.commit().await?;
+
+    Ok(Json(SeriesResponse {
+        series,
+        occurrences,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/series/{id}",
+    tag = "series",
+    responses((status = 200, body = SeriesResponse))
+)]
+async fn get_series(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SeriesResponse>, AppError> {
+    let series = authorize_series(&app_state.db, &user, id, Permission::View).await?;
+    let occurrences = occurrences(id).all(&*app_state.db).await?;
+    Ok(Json(SeriesResponse {
+        series,
+        occurrences: occurrences.into_iter().map(EventResponse::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/series/{id}",
+    tag = "series",
+    request_body = UpdateSeriesRequest,
+    responses((status = 200, body = SeriesResponse))
+)]
+async fn update_series(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateSeriesRequest>,
+) -> Result<Json<SeriesResponse>, AppError> {
+    let series = authorize_series(&app_state.db, &user, id, Permission::Edit).await?;
+
+    let txn = app_state.db.begin().await?;
+    let mut update = event::Entity::update_many()
+        .filter(event::Column::SeriesId.eq(id))
+        .filter(event::Column::SeriesDetached.eq(false));
+    let mut changed = false;
+    if let Some(title) = &body.title {
+        update = update.col_expr(event::Column::Title, Expr::value(title.clone()));
+        changed = true;
+    }
+    if let Some(description) = body.description {
+        update = update.col_expr(event::Column::Description, Expr::value(Some(description)));
+        changed = true;
+    }
+    if let Some(settings) = body.settings {
+        RefundPolicy::from_settings(Some(&settings))?;
+        update = update.col_expr(event::Column::Settings, Expr::value(Some(settings)));
+        changed = true;
+    }
+    if changed {
+        update.exec(&txn).await?;
+    }
+
+    let series = match body.title {
+        Some(title) => {
+            let mut series = series.into_active_model();
+            series.title = Set(title);
+            series.update(&txn).await?
+        }
+        None => series,
+    };
+    let occurrences = occurrences(id).all(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(SeriesResponse {
+        series,
+        occurrences: occurrences.into_iter().map(EventResponse::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/series/{id}/occurrences",
+    tag = "series",
+    params(OccurrenceQuery),
+    responses((status = 200, body = inline(Vec<EventResponse>)))
+)]
+async fn list_occurrences(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<OccurrenceQuery>,
+) -> Result<Json<Vec<EventResponse>>, AppError> {
+    authorize_series(&app_state.db, &user, id, Permission::View).await?;
+
+    let mut select = occurrences(id);
+    if let Some(from) = query.from {
+        select = select.filter(event::Column::StartsAt.gte(to_utc(from)));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(event::Column::StartsAt.lte(to_utc(to)));
+    }
+    Ok(Json(
+        select
+            .all(&*app_state.db)
+            .await?
+            .into_iter()
+            .map(EventResponse::from)
+            .collect(),
+    ))
+}
+
+fn occurrences(series_id: Uuid) -> Select<event::Entity> {
+    event::Entity::find_live()
+        .filter(event::Column::SeriesId.eq(series_id))
+        .order_by_asc(event::Column::StartsAt)
+}
@@ -0,0 +1,77 @@
+use crate::access::authorize_reservation;
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::ticket::{QrQuery, TicketResponse};
+use crate::error::AppError;
+use crate::model::ticket;
+use crate::ticket::render_qr;
+use axum::extract::{Path, Query};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::{Json, extract::State};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn ticket_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_tickets))
+        .routes(routes!(ticket_qr))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reservation/{id}/tickets",
+    tag = "ticket",
+    responses((status = 200, body = Vec<TicketResponse>))
+)]
+async fn list_tickets(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TicketResponse>>, AppError> {
+    authorize_reservation(&app_state.db, &user, id).await?;
+    let tickets = ticket::Entity::find()
+        .filter(ticket::Column::ReservationId.eq(id))
+        .order_by_asc(ticket::Column::SeatLabel)
+        .all(&*app_state.db)
+        .await?;
+    Ok(Json(
+        tickets.into_iter().map(TicketResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ticket/{id}/qr",
+    tag = "ticket",
+    params(QrQuery),
+    responses(
+        (status = 200, description = "QR code of the ticket's token", content(
+            (String = "image/svg+xml"),
+            (Vec<u8> = "image/png")
+        )),
+        (status = 409, description = "The ticket's seat has been cancelled")
+    )
+)]
+async fn ticket_qr(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<QrQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let ticket = ticket::Entity::find_by_id(id)
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Ticket not found".to_string()))?;
+    authorize_reservation(&app_state.db, &user, ticket.reservation_id).await?;
+    if ticket.revoked_at.is_some() {
+        return Err(AppError::Conflict("Ticket has been cancelled".to_string()));
+    }
+
+    let format = query.format.unwrap_or_default();
+    Ok((
+        [(header::CONTENT_TYPE, format.content_type())],
+        render_qr(&ticket.token, format)?,
+    ))
+}
@@ -0,0 +1,87 @@
+use crate::access::{Permission, authorize_workspace};
+use crate::app::AppState;
+use crate::auth::AuthUser;
+use crate::dto::event::EventResponse;
+use crate::dto::trash::TrashResponse;
+use crate::dto::workspace::WorkspaceResponse;
+use crate::error::AppError;
+use crate::model::{event, form, section, workspace};
+use crate::trash::{SoftDelete, TRASH_RETENTION_DAYS};
+use axum::extract::Path;
+use axum::{Json, extract::State};
+use sea_orm::{ColumnTrait, QueryFilter, QueryOrder};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+pub fn trash_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_workspace_trash))
+        .routes(routes!(get_deleted_workspaces))
+}
+
+#[utoipa::path(
+    get,
+    path = "/workspace/{id}/trash",
+    tag = "trash",
+    responses((status = 200, body = TrashResponse))
+)]
+async fn get_workspace_trash(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TrashResponse>, AppError> {
+    authorize_workspace(&app_state.db, &user, id, Permission::Edit).await?;
+
+    let events = event::Entity::find_deleted()
+        .filter(event::Column::WorkspaceId.eq(id))
+        .order_by_desc(event::Column::DeletedAt)
+        .all(&*app_state.db)
+        .await?;
+    let sections = section::Entity::find_deleted()
+        .inner_join(event::Entity)
+        .filter(event::Column::WorkspaceId.eq(id))
+        .filter(event::Column::DeletedAt.is_null())
+        .order_by_desc(section::Column::DeletedAt)
+        .all(&*app_state.db)
+        .await?;
+    let forms = form::Entity::find_deleted()
+        .inner_join(event::Entity)
+        .filter(event::Column::WorkspaceId.eq(id))
+        .filter(event::Column::DeletedAt.is_null())
+        .order_by_desc(form::Column::DeletedAt)
+        .all(&*app_state.db)
+        .await?;
+
+    Ok(Json(TrashResponse {
+        retention_days: TRASH_RETENTION_DAYS,
+        events: events.into_iter().map(EventResponse::from).collect(),
+        sections,
+        forms,
+    }))
+}
+
+/// Deleted workspaces the user owns; only the owner can restore them.
+#[utoipa::path(
+    get,
+    path = "/workspaces/{user_id}/trash",
+    tag = "trash",
+    responses((status = 200, body = inline(Vec<WorkspaceResponse>)))
+)]
+async fn get_deleted_workspaces(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(user_id): Path<String>,
+) -> Result<Json<Vec<WorkspaceResponse>>, AppError> {
+    if user_id != user.id() {
+        return Err(AppError::Forbidden);
+    }
+    let workspaces = workspace::Entity::find_deleted()
+        .filter(workspace::Column::OwnerId.eq(user_id.as_str()))
+        .order_by_desc(workspace::Column::DeletedAt)
+        .all(&*app_state.db)
+        .await?
+        .into_iter()
+        .map(WorkspaceResponse::from)
+        .collect();
+    Ok(Json(workspaces))
+}
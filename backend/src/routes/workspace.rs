@@ -1,23 +1,31 @@
+use crate::access::{MEMBER_ACTIVE, Permission, authorize_workspace, check_workspace};
 use crate::app::AppState;
+use crate::auth::AuthUser;
 use crate::dto::workspace::{DeleteResponse, RenameRequest, WorkspaceRequest, WorkspaceResponse};
 use crate::error::AppError;
-use crate::model::workspace;
+use crate::model::{workspace, workspace_member};
+use crate::trash::{SoftDelete, move_to_trash};
 use axum::extract::{Path, Query};
 use axum::{Json, extract::State};
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IntoActiveModel, QueryFilter,
+    QuerySelect, QueryTrait,
+};
 use std::collections::HashMap;
 use std::iter::Iterator;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 pub fn workspace_routes() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(
-        create_workspace,
-        delete_workspace,
-        rename_workspace,
-        get_workspace
-    ))
+    OpenApiRouter::new()
+        .routes(routes!(
+            create_workspace,
+            delete_workspace,
+            rename_workspace,
+            get_workspace
+        ))
+        .routes(routes!(restore_workspace))
 }
 
 #[utoipa::path(
@@ -29,8 +37,12 @@ pub fn workspace_routes() -> OpenApiRouter<AppState> {
 )]
 async fn create_workspace(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<WorkspaceRequest>,
 ) -> Result<Json<WorkspaceResponse>, AppError> {
+    if body.owner_id != user.id() {
+        return Err(AppError::Forbidden);
+    }
     let workspace = workspace::ActiveModel {
         name: Set(body.name),
         owner_id: Set(body.owner_id),
@@ -55,10 +67,25 @@ pub mod workspaces {
 )]
     async fn get_workspaces(
         State(app_state): State<AppState>,
+        user: AuthUser,
         Path(user_id): Path<String>,
     ) -> Result<Json<Vec<WorkspaceResponse>>, AppError> {
-        let workspaces = workspace::Entity::find()
-            .filter(workspace::Column::OwnerId.eq(user_id))
+        if user_id != user.id() {
+            return Err(AppError::Forbidden);
+        }
+        // Workspaces the user owns plus those they joined as a member.
+        let memberships = workspace_member::Entity::find()
+            .select_only()
+            .column(workspace_member::Column::WorkspaceId)
+            .filter(workspace_member::Column::UserId.eq(user_id.as_str()))
+            .filter(workspace_member::Column::Status.eq(MEMBER_ACTIVE))
+            .into_query();
+        let workspaces = workspace::Entity::find_live()
+            .filter(
+                Condition::any()
+                    .add(workspace::Column::OwnerId.eq(user_id.as_str()))
+                    .add(workspace::Column::Id.in_subquery(memberships)),
+            )
             .all(&*app_state.db)
             .await?
             .into_iter()
@@ -76,13 +103,14 @@ pub mod workspaces {
 )]
 async fn get_workspace(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Path(workspace_id): Path<Uuid>,
 ) -> Result<Json<WorkspaceResponse>, AppError> {
-    let workspace = workspace::Entity::find()
-        .filter(workspace::Column::Id.eq(workspace_id))
+    let workspace = workspace::Entity::find_live_by_id(workspace_id)
         .one(&*app_state.db)
         .await?
         .ok_or(AppError::NotFound("Workspace not found".to_string()))?;
+    check_workspace(&app_state.db, &user, &workspace, Permission::View).await?;
 
     Ok(Json(WorkspaceResponse::from(workspace)))
 }
@@ -98,6 +126,7 @@ async fn get_workspace(
 )]
 async fn delete_workspace(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<DeleteResponse>, AppError> {
     let workspace_id: Uuid = params
@@ -107,18 +136,39 @@ async fn delete_workspace(
         ))?
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid UUID for `workspace_id`".to_string()))?;
+    authorize_workspace(&app_state.db, &user, workspace_id, Permission::Own).await?;
 
-    let result = workspace::Entity::delete_by_id(workspace_id)
-        .exec(&*app_state.db)
-        .await?;
+    // Moved to the trash; its events, sales and members stay until purged.
+    let rows_affected = move_to_trash::<workspace::Entity>(&*app_state.db, workspace_id).await?;
 
-    if result.rows_affected == 0 {
+    if rows_affected == 0 {
         return Err(AppError::NotFound("Workspace not found".to_string()));
     }
 
-    Ok(Json(DeleteResponse {
-        rows_affected: result.rows_affected,
-    }))
+    Ok(Json(DeleteResponse { rows_affected }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/workspace/{id}/restore",
+    tag = "workspace",
+    responses((status = 200, body = WorkspaceResponse))
+)]
+async fn restore_workspace(
+    State(app_state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WorkspaceResponse>, AppError> {
+    let workspace = workspace::Entity::find_deleted_by_id(id)
+        .one(&*app_state.db)
+        .await?
+        .ok_or(AppError::NotFound("Workspace not in trash".to_string()))?;
+    check_workspace(&app_state.db, &user, &workspace, Permission::Own).await?;
+
+    let mut workspace = workspace.into_active_model();
+    workspace.deleted_at = Set(None);
+    let workspace = workspace.update(&*app_state.db).await?;
+    Ok(Json(WorkspaceResponse::from(workspace)))
 }
 
 #[utoipa::path(
@@ -130,12 +180,11 @@ async fn delete_workspace(
 )]
 async fn rename_workspace(
     State(app_state): State<AppState>,
+    user: AuthUser,
     Json(body): Json<RenameRequest>,
 ) -> Result<Json<WorkspaceResponse>, AppError> {
-    let mut workspace = workspace::Entity::find_by_id(body.id)
-        .one(&*app_state.db)
+    let mut workspace = authorize_workspace(&app_state.db, &user, body.id, Permission::Manage)
         .await?
-        .ok_or(AppError::NotFound("Workspace not found".to_string()))?
         .into_active_model();
 
     workspace.name = Set(body.name);
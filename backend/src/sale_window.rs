@@ -0,0 +1,65 @@
+//! Sale windows let a section sell in phases, each at its own price.
+
+use crate::error::AppError;
+use crate::model::{section, section_sale_window};
+use chrono::NaiveDateTime;
+use sea_orm::prelude::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub fn is_open(window: &section_sale_window::Model, now: NaiveDateTime) -> bool {
+    window.opens_at.is_none_or(|opens_at| opens_at <= now)
+        && window.closes_at.is_none_or(|closes_at| now < closes_at)
+}
+
+/// Price a seat of `section` is booked at during its open windows.
+pub fn booking_price(
+    section: &section::Model,
+    windows: &[section_sale_window::Model],
+    now: NaiveDateTime,
+    access_code: Option<&str>,
+) -> Result<Decimal, AppError> {
+    if windows.is_empty() {
+        return Ok(section.price);
+    }
+    let open: Vec<_> = windows
+        .iter()
+        .filter(|window| is_open(window, now))
+        .collect();
+    if open.is_empty() {
+        return Err(AppError::Conflict(format!(
+            "Section `{}` is not on sale",
+            section.title
+        )));
+    }
+    open.into_iter()
+        .filter(|window| match window.access_code.as_deref() {
+            Some(code) => access_code.map(str::trim) == Some(code),
+            None => true,
+        })
+        .min_by_key(|window| window.access_code.is_none())
+        .map(|window| window.price)
+        .ok_or(AppError::Forbidden)
+}
+
+/// Windows of every section in `section_ids`, ordered by opening time.
+pub async fn sale_windows(
+    db: &impl ConnectionTrait,
+    section_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<HashMap<Uuid, Vec<section_sale_window::Model>>, DbErr> {
+    let section_ids: Vec<Uuid> = section_ids.into_iter().collect();
+    if section_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut windows: HashMap<Uuid, Vec<section_sale_window::Model>> = HashMap::new();
+    for window in section_sale_window::Entity::find()
+        .filter(section_sale_window::Column::SectionId.is_in(section_ids))
+        .order_by_asc(section_sale_window::Column::OpensAt)
+        .all(db)
+        .await?
+    {
+        windows.entry(window.section_id).or_default().push(window);
+    }
+    Ok(windows)
+}
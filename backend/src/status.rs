@@ -0,0 +1,107 @@
+//! Values stored in the free-form `status` columns.
+
+/// Availability of an `event_object`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeatStatus {
+    Available,
+    Held,
+    Sold,
+}
+
+impl SeatStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeatStatus::Available => "available",
+            SeatStatus::Held => "held",
+            SeatStatus::Sold => "sold",
+        }
+    }
+}
+
+/// Lifecycle of a `reservation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReservationStatus {
+    /// Seats are held until `expires_at`.
+    Pending,
+    Confirmed,
+    Cancelled,
+    Expired,
+    /// The provider reported the payment as failed; the seats were released.
+    Failed,
+    /// Paid for an event that was cancelled; waiting to be refunded.
+    RefundPending,
+}
+
+impl ReservationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReservationStatus::Pending => "pending",
+            ReservationStatus::Confirmed => "confirmed",
+            ReservationStatus::Cancelled => "cancelled",
+            ReservationStatus::Expired => "expired",
+            ReservationStatus::Failed => "failed",
+            ReservationStatus::RefundPending => "refund_pending",
+        }
+    }
+}
+
+/// Lifecycle of an `event`. Only `OnSale` events take new reservations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventStatus {
+    /// Being prepared; the default for new and copied events.
+    Draft,
+    /// Announced and publicly readable, but not selling yet.
+    Published,
+    OnSale,
+    /// Set automatically once every enabled seat is sold.
+    SoldOut,
+    /// Sales have stopped; can be reopened.
+    Closed,
+    /// Final. Holds were released and sales flagged for refund.
+    Cancelled,
+}
+
+impl EventStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventStatus::Draft => "draft",
+            EventStatus::Published => "published",
+            EventStatus::OnSale => "on_sale",
+            EventStatus::SoldOut => "sold_out",
+            EventStatus::Closed => "closed",
+            EventStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(EventStatus::Draft),
+            "published" => Some(EventStatus::Published),
+            "on_sale" => Some(EventStatus::OnSale),
+            "sold_out" => Some(EventStatus::SoldOut),
+            "closed" => Some(EventStatus::Closed),
+            "cancelled" => Some(EventStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Whether the event can be read by anyone, without workspace access.
+    pub fn is_public(&self) -> bool {
+        matches!(
+            self,
+            EventStatus::Published | EventStatus::OnSale | EventStatus::SoldOut
+        )
+    }
+
+    pub fn can_become(&self, next: EventStatus) -> bool {
+        use EventStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Published)
+                | (Published | Closed | SoldOut, OnSale)
+                | (OnSale, SoldOut)
+                | (OnSale | SoldOut, Closed)
+                | (Draft | Published | OnSale | SoldOut | Closed, Cancelled)
+        )
+    }
+}
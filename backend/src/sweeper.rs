@@ -0,0 +1,52 @@
+use crate::app::AppState;
+use crate::booking::expire_holds;
+use crate::status::SeatStatus;
+use axum_prometheus::metrics::counter;
+use chrono::Utc;
+use sea_orm::DbErr;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+const SWEEP_BATCH_SIZE: u64 = 500;
+
+pub const RELEASED_SEATS_METRIC: &str = "reservation_released_seats_total";
+
+/// Spawns the sweeper that expires lapsed holds every 30 seconds.
+pub fn spawn_reservation_sweeper(app_state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = interval(SWEEP_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = sweep_expired_holds(&app_state).await {
+                error!("Cannot expire reservation holds: {}", err);
+            }
+        }
+    })
+}
+
+pub async fn sweep_expired_holds(app_state: &AppState) -> Result<u64, DbErr> {
+    let now = Utc::now().naive_utc();
+    let mut released = 0;
+    loop {
+        let expired = expire_holds(&app_state.db, now, SWEEP_BATCH_SIZE).await?;
+        counter!(RELEASED_SEATS_METRIC).increment(expired.seats);
+        released += expired.seats;
+        for (event_id, seat_ids) in expired.released {
+            app_state
+                .live
+                .publish(event_id, SeatStatus::Available, seat_ids);
+        }
+        if expired.reservations < SWEEP_BATCH_SIZE {
+            break;
+        }
+    }
+
+    if released > 0 {
+        info!("Released {} seats from expired reservation holds", released);
+    }
+    Ok(released)
+}
@@ -0,0 +1,181 @@
+//! Tickets carry HMAC-signed tokens, shown as QR codes, that gates check offline.
+
+use crate::booking::booked_seat_ids;
+use crate::error::AppError;
+use crate::model::{event_object, reservation_item, ticket};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use eyre::{Result, eyre};
+use hmac::{Hmac, Mac};
+use image::{ImageFormat, Luma};
+use qrcode::QrCode;
+use qrcode::render::svg;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::io::Cursor;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Key used by [`TicketSigner::development`].
+const DEVELOPMENT_SECRET: &str = "development_ticket_secret";
+
+/// Smallest side of a rendered QR code, in pixels.
+const QR_MIN_SIZE: u32 = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketClaims {
+    #[serde(rename = "t")]
+    pub ticket_id: Uuid,
+    #[serde(rename = "e")]
+    pub event_id: Uuid,
+    #[serde(rename = "s")]
+    pub seat_label: String,
+}
+
+#[derive(Clone)]
+pub struct TicketSigner {
+    secret: Arc<[u8]>,
+}
+
+impl fmt::Debug for TicketSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TicketSigner").finish_non_exhaustive()
+    }
+}
+
+impl TicketSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    pub fn development() -> Self {
+        Self::new(DEVELOPMENT_SECRET.as_bytes())
+    }
+
+    pub fn sign(&self, claims: &TicketClaims) -> String {
+        let claims = URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(claims).expect("ticket claims serialize to JSON"));
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(claims.as_bytes()).finalize().into_bytes());
+        format!("{}.{}", claims, signature)
+    }
+
+    /// Claims of `token`, or `None` when it was not signed with this key.
+    pub fn verify(&self, token: &str) -> Option<TicketClaims> {
+        let (claims, signature) = token.trim().split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        self.mac(claims.as_bytes()).verify_slice(&signature).ok()?;
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).ok()?).ok()
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(payload);
+        mac
+    }
+}
+
+pub fn create_ticket_signer() -> Result<TicketSigner> {
+    let secret = env::var("TICKET_SIGNING_SECRET")
+        .map_err(|_| eyre!("TICKET_SIGNING_SECRET must be set"))?;
+    Ok(TicketSigner::new(secret.as_bytes()))
+}
+
+pub async fn issue_tickets(
+    db: &impl ConnectionTrait,
+    signer: &TicketSigner,
+    event_id: Uuid,
+    items: &[reservation_item::Model],
+) -> Result<(), DbErr> {
+    let seat_ids = booked_seat_ids(items);
+    if seat_ids.is_empty() {
+        return Ok(());
+    }
+    let labels: HashMap<Uuid, Option<String>> = event_object::Entity::find()
+        .filter(event_object::Column::Id.is_in(seat_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|seat| (seat.id, seat.label))
+        .collect();
+
+    let tickets = items
+        .iter()
+        .filter(|item| item.cancelled_at.is_none())
+        .map(|item| {
+            let claims = TicketClaims {
+                ticket_id: Uuid::new_v4(),
+                event_id,
+                seat_label: labels
+                    .get(&item.event_object_id)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or_default(),
+            };
+            ticket::ActiveModel {
+                id: Set(claims.ticket_id),
+                reservation_id: Set(item.reservation_id),
+                reservation_item_id: Set(item.id),
+                event_id: Set(event_id),
+                seat_label: Set(claims.seat_label.clone()),
+                token: Set(signer.sign(&claims)),
+                ..Default::default()
+            }
+        });
+    ticket::Entity::insert_many(tickets)
+        .exec_without_returning(db)
+        .await?;
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+impl QrFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QrFormat::Svg => "image/svg+xml",
+            QrFormat::Png => "image/png",
+        }
+    }
+}
+
+pub fn render_qr(token: &str, format: QrFormat) -> Result<Vec<u8>, AppError> {
+    let code = QrCode::new(token.as_bytes()).map_err(|err| {
+        tracing::error!("Cannot encode ticket token as QR code: {}", err);
+        AppError::Internal
+    })?;
+    match format {
+        QrFormat::Svg => Ok(code
+            .render::<svg::Color>()
+            .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+            .build()
+            .into_bytes()),
+        QrFormat::Png => {
+            let image = code
+                .render::<Luma<u8>>()
+                .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+                .build();
+            let mut png = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .map_err(|err| {
+                    tracing::error!("Cannot encode ticket QR code as PNG: {}", err);
+                    AppError::Internal
+                })?;
+            Ok(png)
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::AppError;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+pub fn validate_timezone(name: &str) -> Result<Tz, AppError> {
+    name.trim()
+        .parse()
+        .map_err(|_| AppError::Validation(format!("`timezone` `{}` is not an IANA timezone", name)))
+}
+
+/// Timezone of an event, UTC for values stored before validation.
+pub fn event_timezone(name: &str) -> Tz {
+    name.parse().unwrap_or(Tz::UTC)
+}
+
+pub fn to_utc(time: DateTime<FixedOffset>) -> NaiveDateTime {
+    time.naive_utc()
+}
+
+pub fn to_local(tz: Tz, utc: NaiveDateTime) -> DateTime<FixedOffset> {
+    let local = tz.from_utc_datetime(&utc);
+    local.with_timezone(&local.offset().fix())
+}
+
+/// Converts a wall-clock time in `tz` to UTC, taking the earlier of ambiguous times.
+pub fn local_to_utc(tz: Tz, local: NaiveDateTime) -> NaiveDateTime {
+    if let Some(time) = tz.from_local_datetime(&local).earliest() {
+        return time.naive_utc();
+    }
+    let before = tz
+        .offset_from_utc_datetime(&(local - Duration::days(1)))
+        .fix();
+    local - Duration::seconds(before.local_minus_utc().into())
+}
+
+pub fn validate_range(
+    starts_at: Option<NaiveDateTime>,
+    ends_at: Option<NaiveDateTime>,
+) -> Result<(), AppError> {
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at)
+        && ends_at <= starts_at
+    {
+        return Err(AppError::Validation(
+            "`ends_at` must be after `starts_at`".to_string(),
+        ));
+    }
+    Ok(())
+}
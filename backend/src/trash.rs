@@ -0,0 +1,199 @@
+//! Soft deletion of workspaces, events, sections and forms.
+
+use crate::app::AppState;
+use crate::model::{event, event_object, form, reservation, reservation_item, section, workspace};
+use axum_prometheus::metrics::counter;
+use chrono::{Duration as TimeDelta, NaiveDateTime, Utc};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QuerySelect, QueryTrait, Select,
+};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Days a deleted row can still be restored.
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub const PURGED_ROWS_METRIC: &str = "trash_purged_rows_total";
+
+/// An entity whose rows are moved to the trash instead of being deleted.
+pub trait SoftDelete: EntityTrait {
+    fn id_column() -> Self::Column;
+
+    fn deleted_at_column() -> Self::Column;
+
+    fn find_live() -> Select<Self> {
+        Self::find().filter(Self::deleted_at_column().is_null())
+    }
+
+    fn find_live_by_id(id: Uuid) -> Select<Self> {
+        Self::find_live().filter(Self::id_column().eq(id))
+    }
+
+    fn find_deleted() -> Select<Self> {
+        Self::find().filter(Self::deleted_at_column().is_not_null())
+    }
+
+    fn find_deleted_by_id(id: Uuid) -> Select<Self> {
+        Self::find_deleted().filter(Self::id_column().eq(id))
+    }
+}
+
+impl SoftDelete for workspace::Entity {
+    fn id_column() -> Self::Column {
+        workspace::Column::Id
+    }
+
+    fn deleted_at_column() -> Self::Column {
+        workspace::Column::DeletedAt
+    }
+}
+
+impl SoftDelete for event::Entity {
+    fn id_column() -> Self::Column {
+        event::Column::Id
+    }
+
+    fn deleted_at_column() -> Self::Column {
+        event::Column::DeletedAt
+    }
+}
+
+impl SoftDelete for section::Entity {
+    fn id_column() -> Self::Column {
+        section::Column::Id
+    }
+
+    fn deleted_at_column() -> Self::Column {
+        section::Column::DeletedAt
+    }
+}
+
+impl SoftDelete for form::Entity {
+    fn id_column() -> Self::Column {
+        form::Column::Id
+    }
+
+    fn deleted_at_column() -> Self::Column {
+        form::Column::DeletedAt
+    }
+}
+
+/// Moves a row to the trash, returning 0 when it is missing or already there.
+pub async fn move_to_trash<E: SoftDelete>(
+    db: &impl ConnectionTrait,
+    id: Uuid,
+) -> Result<u64, DbErr> {
+    let result = E::update_many()
+        .col_expr(E::deleted_at_column(), Expr::value(Utc::now().naive_utc()))
+        .filter(E::id_column().eq(id))
+        .filter(E::deleted_at_column().is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Spawns the hourly purge of rows older than [`TRASH_RETENTION_DAYS`].
+pub fn spawn_trash_purger(app_state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = interval(PURGE_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let cutoff = Utc::now().naive_utc() - TimeDelta::days(TRASH_RETENTION_DAYS);
+            match purge_trash(&*app_state.db, cutoff).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} rows from the trash", purged),
+                Err(err) => error!("Cannot purge the trash: {}", err),
+            }
+        }
+    })
+}
+
+/// Deletes what was trashed before `deleted_before`, keeping events that sold anything.
+pub async fn purge_trash(
+    db: &impl ConnectionTrait,
+    deleted_before: NaiveDateTime,
+) -> Result<u64, DbErr> {
+    let event_sales = reservation::Entity::find()
+        .select_only()
+        .expr(Expr::value(1))
+        .filter(
+            Expr::col((reservation::Entity, reservation::Column::EventId))
+                .equals((event::Entity, event::Column::Id)),
+        )
+        .into_query();
+    let workspace_sales = reservation::Entity::find()
+        .select_only()
+        .expr(Expr::value(1))
+        .inner_join(event::Entity)
+        .filter(
+            Expr::col((event::Entity, event::Column::WorkspaceId))
+                .equals((workspace::Entity, workspace::Column::Id)),
+        )
+        .into_query();
+
+    // Seats of a purged section would stay on sale without a price, so they go
+    // with it unless they were booked, which keeps the section in the trash.
+    let booked = reservation_item::Entity::find()
+        .select_only()
+        .expr(Expr::value(1))
+        .filter(
+            Expr::col((
+                reservation_item::Entity,
+                reservation_item::Column::EventObjectId,
+            ))
+            .equals((event_object::Entity, event_object::Column::Id)),
+        )
+        .into_query();
+    let purged_sections = section::Entity::find()
+        .select_only()
+        .column(section::Column::Id)
+        .filter(section::Column::DeletedAt.lt(deleted_before))
+        .into_query();
+    let section_seats = event_object::Entity::find()
+        .select_only()
+        .expr(Expr::value(1))
+        .filter(
+            Expr::col((event_object::Entity, event_object::Column::SectionId))
+                .equals((section::Entity, section::Column::Id)),
+        )
+        .into_query();
+
+    let purged = purge::<form::Entity>(db, deleted_before, None).await?
+        + event_object::Entity::delete_many()
+            .filter(event_object::Column::SectionId.in_subquery(purged_sections))
+            .filter(Expr::exists(booked).not())
+            .exec(db)
+            .await?
+            .rows_affected
+        + purge::<section::Entity>(db, deleted_before, Some(Expr::exists(section_seats).not()))
+            .await?
+        + purge::<event::Entity>(db, deleted_before, Some(Expr::exists(event_sales).not())).await?
+        + purge::<workspace::Entity>(
+            db,
+            deleted_before,
+            Some(Expr::exists(workspace_sales).not()),
+        )
+        .await?;
+    counter!(PURGED_ROWS_METRIC).increment(purged);
+    Ok(purged)
+}
+
+async fn purge<E: SoftDelete>(
+    db: &impl ConnectionTrait,
+    deleted_before: NaiveDateTime,
+    purgeable: Option<SimpleExpr>,
+) -> Result<u64, DbErr> {
+    let mut delete = E::delete_many().filter(E::deleted_at_column().lt(deleted_before));
+    if let Some(purgeable) = purgeable {
+        delete = delete.filter(purgeable);
+    }
+    let result = delete.exec(db).await?;
+    Ok(result.rows_affected)
+}
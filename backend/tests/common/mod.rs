@@ -3,23 +3,91 @@ pub mod helpers {
 
     use axum::Router;
     use backend::{
-        app::create_router,
-        model::{event, form, section, workspace},
+        app::{AppState, create_router},
+        model::{
+            event, event_object, event_object_position, form, reservation, reservation_item,
+            section, section_sale_window, session, user, workspace, workspace_member,
+        },
     };
-    use chrono::{DateTime, NaiveDateTime};
+    use chrono::{DateTime, Duration, NaiveDateTime, Utc};
     use eyre::Result;
     use sea_orm::MockDatabase;
+    use std::sync::Arc;
     use uuid::Uuid;
 
+    pub const TEST_USER_ID: &str = "user_test_nod_prod";
+    pub const TEST_TOKEN: &str = "test_session_token";
+
     pub async fn create_test_app(mock_db: MockDatabase) -> Result<Router> {
         let db = mock_db.into_connection();
-        Ok(create_router(db)?)
+        create_router(AppState::new(Arc::new(db)))
+    }
+
+    /// Queues the session lookup performed by the `AuthUser` extractor.
+    pub fn authenticated(mock_db: MockDatabase) -> MockDatabase {
+        mock_db.append_query_results(vec![vec![(
+            mock_session(TEST_USER_ID, TEST_TOKEN, Duration::hours(1)),
+            mock_user(TEST_USER_ID),
+        )]])
     }
 
     pub fn mock_datetime() -> NaiveDateTime {
         DateTime::from_timestamp(1700000000, 0).unwrap().naive_utc()
     }
 
+    /// Queues an empty membership lookup for a caller who does not own the
+    /// workspace being accessed.
+    pub fn not_a_member(mock_db: MockDatabase) -> MockDatabase {
+        mock_db.append_query_results(vec![Vec::<workspace_member::Model>::new()])
+    }
+
+    pub fn mock_member(
+        workspace_id: Uuid,
+        user_id: Option<&str>,
+        email: &str,
+        role: &str,
+        status: &str,
+    ) -> workspace_member::Model {
+        let now = mock_datetime();
+        workspace_member::Model {
+            id: Uuid::new_v4(),
+            workspace_id,
+            user_id: user_id.map(str::to_string),
+            email: email.to_string(),
+            role: role.to_string(),
+            status: status.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn mock_user(id: &str) -> user::Model {
+        let now = mock_datetime();
+        user::Model {
+            id: id.to_string(),
+            name: "Test User".to_string(),
+            email: format!("{}@example.com", id),
+            email_verified: true,
+            image: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn mock_session(user_id: &str, token: &str, expires_in: Duration) -> session::Model {
+        let now = mock_datetime();
+        session::Model {
+            id: format!("session_{}", user_id),
+            user_id: user_id.to_string(),
+            token: token.to_string(),
+            expires_at: Utc::now().naive_utc() + expires_in,
+            ip_address: None,
+            user_agent: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
     pub fn mock_workspace(id: Uuid, name: &str, owner_id: &str) -> workspace::Model {
         let now = mock_datetime();
         workspace::Model {
@@ -28,15 +96,39 @@ pub mod helpers {
             owner_id: owner_id.to_string(),
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
-    pub fn mock_section(id: Uuid, title: &str, event_id: Uuid, price: f64) -> section::Model {
+    pub fn mock_section(id: Uuid, title: &str, event_id: Uuid, price: &str) -> section::Model {
         let now = mock_datetime();
         section::Model {
             id,
             event_id,
             title: title.to_string(),
-            price,
+            price: price.parse().unwrap(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    pub fn mock_sale_window(
+        section_id: Uuid,
+        name: &str,
+        price: &str,
+        opens_at: Option<NaiveDateTime>,
+        closes_at: Option<NaiveDateTime>,
+        access_code: Option<&str>,
+    ) -> section_sale_window::Model {
+        let now = mock_datetime();
+        section_sale_window::Model {
+            id: Uuid::new_v4(),
+            section_id,
+            name: name.to_string(),
+            price: price.parse().unwrap(),
+            opens_at,
+            closes_at,
+            access_code: access_code.map(str::to_string),
             created_at: now,
             updated_at: now,
         }
@@ -51,7 +143,60 @@ pub mod helpers {
             description: None,
             starts_at: None,
             ends_at: None,
+            currency: "THB".to_string(),
+            timezone: "UTC".to_string(),
             settings: None,
+            series_id: None,
+            series_detached: false,
+            status: "draft".to_string(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    /// An event together with the workspace it belongs to, as returned by the
+    /// ownership lookup in `access::authorize_event`.
+    pub fn mock_event_with_owner(
+        event_id: Uuid,
+        owner_id: &str,
+    ) -> (event::Model, workspace::Model) {
+        let workspace_id = Uuid::new_v4();
+        (
+            mock_event(event_id, "Owned Event", workspace_id),
+            mock_workspace(workspace_id, "Owned Workspace", owner_id),
+        )
+    }
+
+    pub fn mock_event_object(
+        id: Uuid,
+        event_id: Uuid,
+        section_id: Option<Uuid>,
+        label: &str,
+        status: &str,
+    ) -> event_object::Model {
+        let now = mock_datetime();
+        event_object::Model {
+            id,
+            object_type: "seat".to_string(),
+            event_id,
+            section_id,
+            label: Some(label.to_string()),
+            is_enable: true,
+            status: status.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn mock_position(event_object_id: Uuid, x: f64, y: f64) -> event_object_position::Model {
+        let now = mock_datetime();
+        event_object_position::Model {
+            id: Uuid::new_v4(),
+            event_object_id,
+            position_x: x,
+            position_y: y,
+            rotation: 0.0,
             created_at: now,
             updated_at: now,
         }
@@ -68,6 +213,47 @@ pub mod helpers {
             settings: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    pub fn mock_reservation(
+        id: Uuid,
+        event_id: Uuid,
+        user_id: &str,
+        status: &str,
+        total_price: &str,
+    ) -> reservation::Model {
+        let now = mock_datetime();
+        reservation::Model {
+            id,
+            user_id: user_id.to_string(),
+            event_id,
+            status: status.to_string(),
+            total_price: total_price.parse().unwrap(),
+            currency: "THB".to_string(),
+            expires_at: Some(Utc::now().naive_utc() + Duration::minutes(10)),
+            payment_provider: None,
+            payment_intent_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn mock_reservation_item(
+        reservation_id: Uuid,
+        event_object_id: Uuid,
+        price_at_booking: &str,
+    ) -> reservation_item::Model {
+        let now = mock_datetime();
+        reservation_item::Model {
+            id: Uuid::new_v4(),
+            reservation_id,
+            event_object_id,
+            price_at_booking: price_at_booking.parse().unwrap(),
+            cancelled_at: None,
+            created_at: now,
+            updated_at: now,
         }
     }
 }
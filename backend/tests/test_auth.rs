@@ -0,0 +1,95 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::dto::event::EventResponse;
+use backend::model::{session, user};
+use chrono::Duration;
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, create_test_app, mock_event_with_owner, mock_session, mock_user,
+};
+
+#[tokio::test]
+async fn missing_token_is_rejected() -> Result<()> {
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}", Uuid::new_v4()).as_str())
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_token_is_rejected() -> Result<()> {
+    let no_sessions: Vec<(session::Model, user::Model)> = vec![];
+    let mock_db =
+        MockDatabase::new(DatabaseBackend::Postgres).append_query_results(vec![no_sessions]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}", Uuid::new_v4()).as_str())
+        .authorization_bearer("not_a_session")
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+async fn expired_session_is_rejected() -> Result<()> {
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres).append_query_results(vec![vec![(
+        mock_session(TEST_USER_ID, TEST_TOKEN, Duration::hours(-1)),
+        mock_user(TEST_USER_ID),
+    )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}", Uuid::new_v4()).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+async fn session_cookie_is_accepted() -> Result<()> {
+    let id = Uuid::new_v4();
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![(
+            mock_session(TEST_USER_ID, TEST_TOKEN, Duration::hours(1)),
+            mock_user(TEST_USER_ID),
+        )]])
+        .append_query_results(vec![vec![mock_event_with_owner(id, TEST_USER_ID)]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}", id).as_str())
+        .add_header(
+            "cookie",
+            format!(
+                "theme=dark; better-auth.session_token={}.signature",
+                TEST_TOKEN
+            ),
+        )
+        .await;
+
+    response.assert_status_ok();
+    let json: EventResponse = response.json();
+    assert_eq!(json.id, id);
+    Ok(())
+}
@@ -0,0 +1,329 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::check_in::{
+    CheckInRequest, CheckInResponse, CheckInSummaryResponse, GateCheckIns,
+};
+use backend::model::{check_in, ticket};
+use backend::ticket::{TicketClaims, TicketSigner};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event_with_owner,
+    mock_member,
+};
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+/// A ticket for seat A1 of `event_id`.
+fn mock_ticket(event_id: Uuid) -> ticket::Model {
+    let claims = TicketClaims {
+        ticket_id: Uuid::new_v4(),
+        event_id,
+        seat_label: "A1".to_string(),
+    };
+    ticket::Model {
+        id: claims.ticket_id,
+        reservation_id: Uuid::new_v4(),
+        reservation_item_id: Uuid::new_v4(),
+        event_id,
+        seat_label: claims.seat_label.clone(),
+        token: TicketSigner::development().sign(&claims),
+        revoked_at: None,
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    }
+}
+
+fn mock_check_in(ticket: &ticket::Model, gate: &str) -> check_in::Model {
+    let now = mock_datetime();
+    check_in::Model {
+        id: Uuid::new_v4(),
+        ticket_id: ticket.id,
+        event_id: ticket.event_id,
+        gate: gate.to_string(),
+        device: None,
+        operator_id: TEST_USER_ID.to_string(),
+        checked_in_at: now,
+        undone_at: None,
+        undone_by: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn scan(ticket: &ticket::Model) -> CheckInRequest {
+    CheckInRequest {
+        token: ticket.token.clone(),
+        gate: " North ".to_string(),
+        device: Some("scanner-1".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn box_office_checks_a_ticket_in() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = mock_ticket(event_id);
+    let (event, workspace) = mock_event_with_owner(event_id, "someone-else");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event, workspace.clone())]])
+        .append_query_results(vec![vec![mock_member(
+            workspace.id,
+            Some(TEST_USER_ID),
+            "box@office.test",
+            "box_office",
+            "active",
+        )]])
+        .append_query_results(vec![vec![ticket.clone()]])
+        .append_exec_results(vec![rows(1)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await;
+
+    response.assert_status_ok();
+    let check_in = response.json::<CheckInResponse>();
+    assert_eq!(check_in.ticket_id, ticket.id);
+    assert_eq!(check_in.gate, "North");
+    assert_eq!(check_in.device.as_deref(), Some("scanner-1"));
+    assert_eq!(check_in.operator_id, TEST_USER_ID);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = log
+        .iter()
+        .flat_map(|transaction| transaction.statements())
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "check_in""#))
+        .unwrap()
+        .clone();
+    assert!(
+        insert
+            .sql
+            .contains(r#"ON CONFLICT ("ticket_id") WHERE "undone_at" IS NULL DO NOTHING"#)
+    );
+    assert!(insert.values.unwrap().0.contains(&Value::from("North")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn second_scan_says_where_the_ticket_was_checked_in() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = mock_ticket(event_id);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![ticket.clone()]])
+        .append_exec_results(vec![rows(0)])
+        .append_query_results(vec![vec![mock_check_in(&ticket, "South")]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    let details = response.json::<serde_json::Value>()["details"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(details.contains("A1 was already checked in at gate South"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn forged_token_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = ticket::Model {
+        token: TicketSigner::new(b"another key").sign(&TicketClaims {
+            ticket_id: Uuid::new_v4(),
+            event_id,
+            seat_label: "A1".to_string(),
+        }),
+        ..mock_ticket(event_id)
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticket_of_a_cancelled_seat_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = ticket::Model {
+        revoked_at: Some(mock_datetime()),
+        ..mock_ticket(event_id)
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![ticket.clone()]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticket_of_another_event_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = mock_ticket(Uuid::new_v4());
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn editor_cannot_check_tickets_in() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = mock_ticket(event_id);
+    let (event, workspace) = mock_event_with_owner(event_id, "someone-else");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event, workspace.clone())]])
+        .append_query_results(vec![vec![mock_member(
+            workspace.id,
+            Some(TEST_USER_ID),
+            "editor@office.test",
+            "editor",
+            "active",
+        )]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/check_ins", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(scan(&ticket)))
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
+
+#[tokio::test]
+async fn undo_check_in() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let ticket = mock_ticket(event_id);
+    let check_in = mock_check_in(&ticket, "North");
+    let undone = check_in::Model {
+        undone_at: Some(mock_datetime()),
+        undone_by: Some(TEST_USER_ID.to_string()),
+        ..check_in.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![undone.clone()]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/check_ins/{}/undo", event_id, check_in.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&CheckInResponse::from(undone));
+    Ok(())
+}
+
+#[tokio::test]
+async fn undone_check_in_cannot_be_undone_again() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![Vec::<check_in::Model>::new()]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/check_ins/{}/undo", event_id, Uuid::new_v4()).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_not_found();
+    Ok(())
+}
+
+fn gate_count(gate: &str, checked_in: i64) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("gate", Value::from(gate)),
+        ("checked_in", Value::from(checked_in)),
+    ])
+}
+
+#[tokio::test]
+async fn summary_counts_check_ins_by_gate() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![BTreeMap::from([(
+            "tickets",
+            Value::from(10i64),
+        )])]])
+        .append_query_results(vec![vec![gate_count("North", 3), gate_count("South", 1)]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/check_ins/summary", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&CheckInSummaryResponse {
+        tickets: 10,
+        checked_in: 4,
+        remaining: 6,
+        gates: vec![
+            GateCheckIns {
+                gate: "North".to_string(),
+                checked_in: 3,
+            },
+            GateCheckIns {
+                gate: "South".to_string(),
+                checked_in: 1,
+            },
+        ],
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let gates = &log.last().unwrap().statements()[0];
+    assert!(gates.sql.contains(r#"INNER JOIN "ticket""#));
+    assert!(gates.sql.contains(r#""ticket"."revoked_at" IS NULL"#));
+    Ok(())
+}
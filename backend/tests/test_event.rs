@@ -1,13 +1,27 @@
+use axum::http::StatusCode;
 use axum_test::TestServer;
-use backend::dto::event::{EventRequest, EventResponse, UpdateEventRequest};
+use backend::app::{AppState, create_router};
+use backend::dto::event::{
+    CloneEventRequest, EventDetailResponse, EventDetailSection, EventPage, EventRequest,
+    EventResponse, UpdateEventRequest,
+};
+use backend::dto::layout::LayoutObject;
 use backend::dto::workspace::DeleteResponse;
+use backend::model::{event, event_object, event_object_position, reservation, workspace};
+use backend::timezone::{local_to_utc, validate_timezone};
+use chrono::{DateTime, Duration, NaiveDateTime};
 use eyre::Result;
-use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
-use serde_json::json;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement};
+use serde_json::{Value, json};
+use std::sync::Arc;
 use uuid::Uuid;
 
 mod common;
-use crate::common::helpers::{create_test_app, mock_event};
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event,
+    mock_event_object, mock_event_with_owner, mock_form, mock_position, mock_sale_window,
+    mock_section, mock_workspace, not_a_member,
+};
 
 #[tokio::test]
 async fn get_event() -> Result<()> {
@@ -16,14 +30,18 @@ async fn get_event() -> Result<()> {
     let title = "Test Event";
 
     let mock_data = mock_event(id, title, workspace_id);
+    let workspace = mock_workspace(workspace_id, "Test Workspace", TEST_USER_ID);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
-        .append_query_results(vec![vec![mock_data.clone()]]);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(mock_data.clone(), workspace)]]);
 
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
 
-    let response = server.get(format!("/event/{}", id).as_str()).await;
+    let response = server
+        .get(format!("/event/{}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
 
     response.assert_status_ok();
     let json: EventResponse = response.json();
@@ -40,7 +58,12 @@ async fn create_event() -> Result<()> {
 
     let expected = mock_event(id, title, workspace_id);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "Test Workspace",
+            TEST_USER_ID,
+        )]])
         .append_query_results(vec![vec![expected.clone()]]);
 
     let app = create_test_app(mock_db).await?;
@@ -48,12 +71,15 @@ async fn create_event() -> Result<()> {
 
     let response = server
         .post("/event")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(EventRequest {
             title: title.to_string(),
             workspace_id,
             description: None,
             starts_at: None,
             ends_at: None,
+            timezone: None,
+            currency: None,
             settings: None,
         }))
         .await;
@@ -68,8 +94,10 @@ async fn create_event() -> Result<()> {
 async fn delete_event() -> Result<()> {
     let id = Uuid::new_v4();
 
-    let mock_db =
-        MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(vec![MockExecResult {
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(id, TEST_USER_ID)]])
+        .append_query_results(vec![Vec::<reservation::Model>::new()])
+        .append_exec_results(vec![MockExecResult {
             rows_affected: 1,
             last_insert_id: 0,
         }]);
@@ -79,6 +107,7 @@ async fn delete_event() -> Result<()> {
 
     let response = server
         .delete(format!("/event?event_id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
         .await;
 
     response.assert_status_ok();
@@ -96,9 +125,10 @@ async fn update_event() -> Result<()> {
 
     let mock_old = mock_event(id, old_title, workspace_id);
     let mock_new = mock_event(id, new_title, workspace_id);
+    let workspace = mock_workspace(workspace_id, "Test Workspace", TEST_USER_ID);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
-        .append_query_results(vec![vec![mock_old.clone()]])
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(mock_old.clone(), workspace)]])
         .append_query_results(vec![vec![mock_new.clone()]]);
 
     let app = create_test_app(mock_db).await?;
@@ -106,12 +136,15 @@ async fn update_event() -> Result<()> {
 
     let response = server
         .put("/event")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(UpdateEventRequest {
             id,
             title: Some(new_title.to_string()),
             description: None,
             starts_at: None,
             ends_at: None,
+            timezone: None,
+            currency: None,
             settings: None,
         }))
         .await;
@@ -122,3 +155,559 @@ async fn update_event() -> Result<()> {
     assert_eq!(json.title, new_title);
     Ok(())
 }
+
+#[tokio::test]
+async fn get_event_of_other_workspace_is_forbidden() -> Result<()> {
+    let id = Uuid::new_v4();
+
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![mock_event_with_owner(id, "someone_else")]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_event_in_other_workspace_is_forbidden() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+
+    let workspace = mock_workspace(workspace_id, "Foreign Workspace", "someone_else");
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![workspace]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/event")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(EventRequest {
+            title: "Hijacked Event".to_string(),
+            workspace_id,
+            description: None,
+            starts_at: None,
+            ends_at: None,
+            timezone: None,
+            currency: None,
+            settings: None,
+        }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_missing_event_is_not_found() -> Result<()> {
+    let no_events: Vec<(event::Model, workspace::Model)> = vec![];
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![no_events]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .delete(format!("/event?event_id={}", Uuid::new_v4()).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_events_returns_a_cursor_for_the_next_page() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let events: Vec<event::Model> = ["Alpha", "Beta", "Gamma"]
+        .into_iter()
+        .map(|title| mock_event(Uuid::new_v4(), title, workspace_id))
+        .collect();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![events.clone()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/workspace/{}/events", workspace_id).as_str())
+        .add_query_param("limit", 2)
+        .add_query_param("sort", "title")
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    let page = response.json::<EventPage>();
+    assert_eq!(
+        page.items,
+        events[..2]
+            .iter()
+            .cloned()
+            .map(EventResponse::from)
+            .collect::<Vec<_>>()
+    );
+    assert!(page.next_cursor.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_events_filters_by_utc_time() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![Vec::<event::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .get(format!("/workspace/{}/events", workspace_id).as_str())
+        .add_query_param("to", "2026-03-01T07:00:00+07:00")
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let values = log.last().unwrap().statements()[0]
+        .values
+        .clone()
+        .unwrap()
+        .0;
+    assert!(
+        values.contains(&sea_orm::Value::from(NaiveDateTime::parse_from_str(
+            "2026-03-01 00:00:00",
+            "%Y-%m-%d %H:%M:%S"
+        )?))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_events_continues_after_cursor() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+
+    // Fetch a first page to obtain a cursor pointing at "Beta".
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![
+            mock_event(Uuid::new_v4(), "Beta", workspace_id),
+            mock_event(Uuid::new_v4(), "Gamma", workspace_id),
+        ]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+    let cursor = server
+        .get(format!("/workspace/{}/events", workspace_id).as_str())
+        .add_query_param("limit", 1)
+        .add_query_param("sort", "title")
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .json::<EventPage>()
+        .next_cursor
+        .unwrap();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![mock_event(
+            Uuid::new_v4(),
+            "Gamma",
+            workspace_id,
+        )]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .get(format!("/workspace/{}/events", workspace_id).as_str())
+        .add_query_param("sort", "title")
+        .add_query_param("q", "50%_off")
+        .add_query_param("cursor", &cursor)
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<EventPage>().next_cursor, None);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let select = log.last().unwrap().statements()[0].clone();
+    assert!(
+        select
+            .sql
+            .contains(r#""event"."title" ILIKE ($2 ESCAPE E'\\')"#)
+    );
+    assert!(select.sql.contains(r#""event"."title" > $3"#));
+    assert!(
+        select
+            .sql
+            .ends_with(r#"ORDER BY "event"."title" ASC, "event"."id" ASC LIMIT $6"#)
+    );
+    let values = select.values.unwrap().0;
+    assert!(values.contains(&sea_orm::Value::from(r"%50\%\_off%".to_string())));
+    assert!(values.contains(&sea_orm::Value::from("Beta".to_string())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_events_rejects_a_cursor_for_another_sort() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![
+            mock_event(Uuid::new_v4(), "Beta", workspace_id),
+            mock_event(Uuid::new_v4(), "Gamma", workspace_id),
+        ]]);
+    let mock_db = authenticated(mock_db).append_query_results(vec![vec![mock_workspace(
+        workspace_id,
+        "test",
+        TEST_USER_ID,
+    )]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+    let path = format!("/workspace/{}/events", workspace_id);
+
+    let cursor = server
+        .get(&path)
+        .add_query_param("limit", 1)
+        .add_query_param("sort", "title")
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .json::<EventPage>()
+        .next_cursor
+        .unwrap();
+
+    server
+        .get(&path)
+        .add_query_param("sort", "created_at")
+        .add_query_param("cursor", &cursor)
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_event_detail() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let event = mock_event_with_owner(event_id, TEST_USER_ID);
+    let floor = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let balcony = mock_section(Uuid::new_v4(), "Balcony", event_id, "10.00");
+    let seat = mock_event_object(Uuid::new_v4(), event_id, Some(floor.id), "A1", "available");
+    let stage = event_object::Model {
+        object_type: "stage".to_string(),
+        ..mock_event_object(Uuid::new_v4(), event_id, None, "Stage", "available")
+    };
+    let position = mock_position(seat.id, 10.0, 20.0);
+    let objects: Vec<(event_object::Model, Option<event_object_position::Model>)> = vec![
+        (seat.clone(), Some(position.clone())),
+        (stage.clone(), None),
+    ];
+    let form = mock_form(Uuid::new_v4(), event_id, "Attendees", "Who is coming");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event.clone()]])
+        .append_query_results(vec![vec![floor.clone(), balcony.clone()]])
+        .append_query_results(vec![objects])
+        .append_query_results(vec![vec![form.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/full", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventDetailResponse {
+        event: EventResponse::from(event.0),
+        sections: vec![
+            EventDetailSection {
+                section: floor,
+                objects: vec![LayoutObject::new(seat, Some(position))],
+            },
+            EventDetailSection {
+                section: balcony,
+                objects: vec![],
+            },
+        ],
+        unsectioned_objects: vec![LayoutObject::new(stage, None)],
+        forms: vec![form],
+    });
+    Ok(())
+}
+
+#[tokio::test]
+async fn event_detail_is_in_the_openapi_spec() -> Result<()> {
+    let app = create_test_app(MockDatabase::new(DatabaseBackend::Postgres)).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let spec = server.get("/api-docs/openapi.json").await.json::<Value>();
+    assert!(spec["paths"]["/event/{id}/full"]["get"].is_object());
+    assert!(spec["components"]["schemas"]["EventDetailResponse"].is_object());
+    Ok(())
+}
+
+fn inserted_ids(statement: &Statement) -> Vec<sea_orm::Value> {
+    statement
+        .values
+        .as_ref()
+        .unwrap()
+        .0
+        .iter()
+        .filter(|value| matches!(value, sea_orm::Value::Uuid(_)))
+        .cloned()
+        .collect()
+}
+
+#[tokio::test]
+async fn clone_event_remaps_the_layout() -> Result<()> {
+    let source_id = Uuid::new_v4();
+    let (source, workspace) = mock_event_with_owner(source_id, TEST_USER_ID);
+    let source = event::Model {
+        starts_at: Some(mock_datetime()),
+        ends_at: Some(mock_datetime() + Duration::hours(3)),
+        ..source
+    };
+    let section = mock_section(Uuid::new_v4(), "Floor", source_id, "25.00");
+    let presale = mock_sale_window(
+        section.id,
+        "Presale",
+        "20.00",
+        Some(mock_datetime() - Duration::days(14)),
+        Some(mock_datetime() - Duration::days(7)),
+        Some("FANS"),
+    );
+    let seat = mock_event_object(Uuid::new_v4(), source_id, Some(section.id), "A1", "sold");
+    let position = mock_position(seat.id, 10.0, 20.0);
+    let form = mock_form(Uuid::new_v4(), source_id, "Attendees", "Who is coming");
+    let starts_at = mock_datetime() + Duration::days(7);
+    let copy = event::Model {
+        id: Uuid::new_v4(),
+        starts_at: Some(starts_at),
+        ends_at: Some(starts_at + Duration::hours(3)),
+        ..source.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(source.clone(), workspace.clone())]])
+        .append_query_results(vec![vec![workspace]])
+        .append_query_results(vec![vec![section.clone()]])
+        .append_query_results(vec![vec![presale]])
+        .append_query_results(vec![vec![seat.clone()]])
+        .append_query_results(vec![vec![position]])
+        .append_query_results(vec![vec![form]])
+        .append_query_results(vec![vec![copy.clone()]])
+        .append_exec_results(vec![
+            MockExecResult {
+                rows_affected: 1,
+                last_insert_id: 0,
+            };
+            5
+        ]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/clone", source_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CloneEventRequest {
+            starts_at: Some(starts_at.and_utc().fixed_offset()),
+            ..Default::default()
+        }))
+        .await;
+    response.assert_status_ok();
+    response.assert_json(&EventResponse::from(copy.clone()));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    let insert = |table: &str| {
+        statements
+            .iter()
+            .find(|statement| {
+                statement
+                    .sql
+                    .starts_with(&format!(r#"INSERT INTO "{}" "#, table))
+            })
+            .unwrap()
+    };
+
+    let event_insert = insert("event").values.as_ref().unwrap().0.clone();
+    assert!(event_insert.contains(&sea_orm::Value::from(starts_at + Duration::hours(3))));
+
+    let new_section = inserted_ids(insert("section"));
+    assert!(!new_section.contains(&sea_orm::Value::from(section.id)));
+    // The presale moves by the week the event moved.
+    let window_insert = insert("section_sale_window");
+    assert!(inserted_ids(window_insert).contains(&new_section[0]));
+    assert!(
+        window_insert
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&sea_orm::Value::from(mock_datetime()))
+    );
+    let new_seat = inserted_ids(insert("event_object"));
+    assert!(new_seat.contains(&new_section[0]));
+    assert!(
+        insert("event_object")
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&sea_orm::Value::from("available"))
+    );
+    assert!(inserted_ids(insert("event_object_position")).contains(&new_seat[0]));
+    assert!(!inserted_ids(insert("form")).contains(&sea_orm::Value::from(source_id)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_event_stores_utc_and_returns_local_time() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let starts_at = DateTime::parse_from_rfc3339("2025-01-03T19:30:00+07:00")?;
+    let expected = event::Model {
+        starts_at: Some(starts_at.naive_utc()),
+        timezone: "Asia/Bangkok".to_string(),
+        ..mock_event(Uuid::new_v4(), "Concert", workspace_id)
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![expected.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post("/event")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(EventRequest {
+            title: "Concert".to_string(),
+            workspace_id,
+            description: None,
+            starts_at: Some(starts_at),
+            ends_at: None,
+            timezone: Some("Asia/Bangkok".to_string()),
+            currency: None,
+            settings: None,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let event = response.json::<EventResponse>();
+    assert_eq!(event.starts_at, Some("2025-01-03T12:30:00".parse()?));
+    assert_eq!(event.starts_at_local, Some(starts_at));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = &log.last().unwrap().statements()[0];
+    assert!(
+        insert
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&sea_orm::Value::from(starts_at.naive_utc()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_event_validates_times_and_timezone() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let starts_at = DateTime::parse_from_rfc3339("2025-01-03T19:30:00+07:00")?;
+    let mut mock_db = MockDatabase::new(DatabaseBackend::Postgres);
+    for _ in 0..2 {
+        mock_db = authenticated(mock_db).append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]]);
+    }
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+    let request = |ends_at, timezone: &str| EventRequest {
+        title: "Concert".to_string(),
+        workspace_id,
+        description: None,
+        starts_at: Some(starts_at),
+        ends_at,
+        timezone: Some(timezone.to_string()),
+        currency: None,
+        settings: None,
+    };
+
+    // 19:30 in Bangkok is 12:30 UTC, so an end at noon UTC comes first.
+    server
+        .post("/event")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(request(
+            Some(DateTime::parse_from_rfc3339("2025-01-03T12:00:00+00:00")?),
+            "Asia/Bangkok"
+        )))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .post("/event")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(request(None, "Mars/Olympus_Mons")))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[test]
+fn local_times_follow_daylight_saving() {
+    let tz = validate_timezone("Europe/London").unwrap();
+    let before: NaiveDateTime = "2025-03-28T19:30:00".parse().unwrap();
+    let after: NaiveDateTime = "2025-04-04T19:30:00".parse().unwrap();
+
+    assert_eq!(local_to_utc(tz, before), before);
+    assert_eq!(local_to_utc(tz, after), after - Duration::hours(1));
+    // 01:30 does not exist on the morning the clocks go forward.
+    assert_eq!(
+        local_to_utc(tz, "2025-03-30T01:30:00".parse().unwrap()),
+        "2025-03-30T01:30:00".parse::<NaiveDateTime>().unwrap()
+    );
+}
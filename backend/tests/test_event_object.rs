@@ -0,0 +1,426 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::event_object::{
+    BulkEventObjectItem, BulkEventObjectRequest, EventObjectRequest, EventObjectResponse,
+};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event_object,
+    mock_event_with_owner, mock_reservation_item, mock_section,
+};
+
+#[tokio::test]
+async fn get_event_objects() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let objects = vec![
+        mock_event_object(Uuid::new_v4(), event_id, None, "A1", "available"),
+        mock_event_object(Uuid::new_v4(), event_id, None, "A2", "sold"),
+    ];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![objects.clone()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/objects", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    let expected: Vec<EventObjectResponse> =
+        objects.into_iter().map(EventObjectResponse::from).collect();
+    response.assert_json(&expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_event_object() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let expected = mock_event_object(Uuid::new_v4(), event_id, None, "A1", "available");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![expected.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/objects", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(EventObjectRequest {
+            object_type: "seat".to_string(),
+            section_id: None,
+            label: Some("A1".to_string()),
+            is_enable: None,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventObjectResponse::from(expected));
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_event_objects_in_bulk() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section_id = Uuid::new_v4();
+    let existing_id = Uuid::new_v4();
+    let existing = mock_event_object(existing_id, event_id, None, "A1", "available");
+    let moved = mock_event_object(existing_id, event_id, Some(section_id), "A1", "available");
+    let created = vec![
+        mock_event_object(
+            Uuid::new_v4(),
+            event_id,
+            Some(section_id),
+            "A2",
+            "available",
+        ),
+        mock_event_object(
+            Uuid::new_v4(),
+            event_id,
+            Some(section_id),
+            "A3",
+            "available",
+        ),
+    ];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![mock_section(
+            section_id, "Floor", event_id, "10.00",
+        )]])
+        .append_query_results(vec![vec![existing]])
+        .append_query_results(vec![vec![moved.clone()]])
+        .append_query_results(vec![created.clone()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let item = |id: Option<Uuid>, label: &str| BulkEventObjectItem {
+        id,
+        object_type: "seat".to_string(),
+        section_id: Some(Some(section_id)),
+        label: Some(Some(label.to_string())),
+        is_enable: None,
+    };
+    let response = server
+        .put(format!("/event/{}/objects/bulk", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(BulkEventObjectRequest {
+            objects: vec![
+                item(None, "A2"),
+                item(Some(existing_id), "A1"),
+                item(None, "A3")
+            ],
+        }))
+        .await;
+
+    // Answered in the order of the request.
+    response.assert_status_ok();
+    let expected: Vec<EventObjectResponse> = [&created[0], &moved, &created[1]]
+        .into_iter()
+        .cloned()
+        .map(EventObjectResponse::from)
+        .collect();
+    response.assert_json(&expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_event_objects_keeps_omitted_fields() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section_id = Uuid::new_v4();
+    let existing_id = Uuid::new_v4();
+    let existing = mock_event_object(existing_id, event_id, Some(section_id), "A1", "available");
+    let disabled = backend::model::event_object::Model {
+        is_enable: false,
+        ..existing.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![existing]])
+        .append_query_results(vec![vec![disabled.clone()]]);
+
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/objects/bulk", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(BulkEventObjectRequest {
+            objects: vec![BulkEventObjectItem {
+                id: Some(existing_id),
+                object_type: "seat".to_string(),
+                section_id: None,
+                label: None,
+                is_enable: Some(false),
+            }],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![EventObjectResponse::from(disabled)]);
+    drop(server);
+
+    // One upsert writes the label and section the item left out unchanged.
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let upsert = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "event_object""#))
+        .unwrap()
+        .clone();
+    assert!(upsert.sql.contains("ON CONFLICT"));
+    let values = upsert.values.unwrap().0;
+    assert!(values.contains(&Value::from("A1")));
+    assert!(values.contains(&Value::from(section_id)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_event_objects_clears_null_fields() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let existing_id = Uuid::new_v4();
+    let existing = mock_event_object(
+        existing_id,
+        event_id,
+        Some(Uuid::new_v4()),
+        "A1",
+        "available",
+    );
+    let cleared = backend::model::event_object::Model {
+        section_id: None,
+        label: None,
+        ..existing.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![existing]])
+        .append_query_results(vec![vec![cleared.clone()]]);
+
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/objects/bulk", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!({
+            "objects": [{
+                "id": existing_id,
+                "object_type": "seat",
+                "section_id": null,
+                "label": null,
+            }],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![EventObjectResponse::from(cleared)]);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let upsert = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "event_object""#))
+        .unwrap()
+        .clone();
+    let values = upsert.values.unwrap().0;
+    assert!(values.contains(&Value::Uuid(None)));
+    assert!(values.contains(&Value::String(None)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_event_object_locks_it_and_clears_null_fields() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let object_id = Uuid::new_v4();
+    let existing = mock_event_object(object_id, event_id, Some(Uuid::new_v4()), "A1", "available");
+    let cleared = backend::model::event_object::Model {
+        section_id: None,
+        ..existing.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![existing]])
+        .append_query_results(vec![vec![cleared.clone()]]);
+
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/objects/{}", event_id, object_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!({ "section_id": null }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventObjectResponse::from(cleared));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert!(statements.iter().any(|statement| {
+        statement.sql.starts_with(r#"SELECT "event_object""#)
+            && statement.sql.ends_with("FOR UPDATE")
+    }));
+    let update = statements
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"UPDATE "event_object""#))
+        .unwrap();
+    assert!(
+        update
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&Value::Uuid(None))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_event_objects_rejects_relabelling_sold_seats() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let sold_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![mock_event_object(
+            sold_id, event_id, None, "A1", "sold",
+        )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/objects/bulk", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(BulkEventObjectRequest {
+            objects: vec![BulkEventObjectItem {
+                id: Some(sold_id),
+                object_type: "seat".to_string(),
+                section_id: None,
+                label: Some(Some("B7".to_string())),
+                is_enable: None,
+            }],
+        }))
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_event_objects_rejects_foreign_section() -> Result<()> {
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![Vec::<backend::model::section::Model>::new()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/objects/bulk", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(BulkEventObjectRequest {
+            objects: vec![BulkEventObjectItem {
+                id: None,
+                object_type: "table".to_string(),
+                section_id: Some(Some(Uuid::new_v4())),
+                label: Some(Some("T1".to_string())),
+                is_enable: None,
+            }],
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_sold_event_object_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let object_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![mock_event_object(
+            object_id, event_id, None, "A1", "sold",
+        )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .delete(format!("/event/{}/objects/{}", event_id, object_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_booked_event_object_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let object_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![mock_event_object(
+            object_id,
+            event_id,
+            None,
+            "A1",
+            "available",
+        )]])
+        .append_query_results(vec![vec![mock_reservation_item(
+            Uuid::new_v4(),
+            object_id,
+            "25.00",
+        )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .delete(format!("/event/{}/objects/{}", event_id, object_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    assert!(
+        response.json::<serde_json::Value>()["details"]
+            .as_str()
+            .unwrap()
+            .contains("is_enable")
+    );
+    Ok(())
+}
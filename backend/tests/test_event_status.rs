@@ -0,0 +1,339 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::event::{CancelEventResponse, EventDetailResponse, EventResponse};
+use backend::dto::reservation::ReservationRequest;
+use backend::model::{
+    event, event_object, event_object_position, form, reservation, reservation_discount, section,
+};
+use backend::status::EventStatus;
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event, mock_event_object,
+    mock_event_with_owner, mock_reservation, mock_reservation_item,
+};
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+fn with_status(event: event::Model, status: EventStatus) -> event::Model {
+    event::Model {
+        status: status.as_str().to_string(),
+        ..event
+    }
+}
+
+#[test]
+fn lifecycle_transitions() {
+    use EventStatus::*;
+    assert!(Draft.can_become(Published));
+    assert!(Published.can_become(OnSale));
+    assert!(Closed.can_become(OnSale));
+    assert!(SoldOut.can_become(Closed));
+    assert!(OnSale.can_become(Cancelled));
+    assert!(!Draft.can_become(OnSale));
+    assert!(!OnSale.can_become(Draft));
+    assert!(!Cancelled.can_become(OnSale));
+    assert!(!Cancelled.can_become(Cancelled));
+}
+
+#[tokio::test]
+async fn publish_draft_event() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let published = with_status(event.clone(), EventStatus::Published);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![vec![published.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/publish", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventResponse::from(published));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let update = &log.last().unwrap().statements()[0];
+    assert!(
+        update
+            .sql
+            .starts_with(r#"UPDATE "event" SET "status" = $1"#)
+    );
+    assert!(update.sql.contains(r#""event"."status" = $3"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn open_sales_of_draft_event_conflicts() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/open", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reopen_sold_out_event_without_seats_left_conflicts() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let event = with_status(event, EventStatus::SoldOut);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![Vec::<event_object::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post(format!("/event/{}/open", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        log.iter()
+            .flat_map(|transaction| transaction.statements())
+            .all(|statement| !statement.sql.starts_with(r#"UPDATE "event""#))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn reopen_closed_event_with_seats_left() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let event = with_status(event, EventStatus::Closed);
+    let on_sale = with_status(event.clone(), EventStatus::OnSale);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![vec![mock_event_object(
+            Uuid::new_v4(),
+            event.id,
+            None,
+            "A1",
+            "available",
+        )]])
+        .append_query_results(vec![vec![on_sale.clone()]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/open", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventResponse::from(on_sale));
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrent_transition_conflicts() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let event = with_status(event, EventStatus::OnSale);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![Vec::<event::Model>::new()]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/event/{}/close", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_event_releases_holds_and_flags_refunds() -> Result<()> {
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let event = with_status(event, EventStatus::OnSale);
+    let cancelled = with_status(event.clone(), EventStatus::Cancelled);
+    let pending = mock_reservation(Uuid::new_v4(), event.id, "buyer", "pending", "25.00");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![vec![cancelled.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![mock_reservation_item(
+            pending.id,
+            Uuid::new_v4(),
+            "25.00",
+        )]])
+        .append_exec_results(vec![rows(1), rows(1), rows(2)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/event/{}/cancel", event.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&CancelEventResponse {
+        event: EventResponse::from(cancelled),
+        cancelled_holds: 1,
+        refunds_pending: 2,
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert_eq!(statements[0].sql, "BEGIN");
+    assert!(statements[1].sql.starts_with(r#"UPDATE "event""#));
+    assert!(statements[2].sql.ends_with("FOR UPDATE"));
+    let refunds = &statements[6];
+    assert!(
+        refunds
+            .sql
+            .starts_with(r#"UPDATE "reservation" SET "status" = $1"#)
+    );
+    assert_eq!(
+        refunds.values.as_ref().unwrap().0[0],
+        sea_orm::Value::from("refund_pending")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn reservation_for_event_not_on_sale_conflicts() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let event = with_status(
+        mock_event(event_id, "Concert", Uuid::new_v4()),
+        EventStatus::Published,
+    );
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![Uuid::new_v4()],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn confirming_the_last_seat_marks_the_event_sold_out() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let confirmed = reservation::Model {
+        status: "confirmed".to_string(),
+        expires_at: None,
+        ..pending.clone()
+    };
+    let item = mock_reservation_item(pending.id, Uuid::new_v4(), "25.00");
+    let seat = mock_event_object(item.event_object_id, pending.event_id, None, "A1", "sold");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![item]])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1), rows(1), rows(1)])
+        .append_query_results(vec![vec![confirmed]])
+        .append_query_results(vec![vec![seat]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert_eq!(statements.last().unwrap().sql, "COMMIT");
+    let sold_out = statements
+        .iter()
+        .find(|statement| {
+            statement
+                .sql
+                .starts_with(r#"UPDATE "event" SET "status" = $1"#)
+        })
+        .unwrap();
+    assert!(sold_out.sql.contains("NOT EXISTS"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn published_event_can_be_read_without_signing_in() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let event = with_status(
+        mock_event(event_id, "Concert", Uuid::new_v4()),
+        EventStatus::Published,
+    );
+    let objects: Vec<(event_object::Model, Option<event_object_position::Model>)> = vec![];
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![event.clone()]])
+        .append_query_results(vec![Vec::<section::Model>::new()])
+        .append_query_results(vec![objects])
+        .append_query_results(vec![Vec::<form::Model>::new()]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .get(format!("/public/event/{}", event_id).as_str())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventDetailResponse {
+        event: EventResponse::from(event),
+        sections: vec![],
+        unsectioned_objects: vec![],
+        forms: vec![],
+    });
+    Ok(())
+}
+
+#[tokio::test]
+async fn draft_event_is_not_public() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![mock_event(event_id, "Concert", Uuid::new_v4())]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .get(format!("/public/event/{}", event_id).as_str())
+        .await
+        .assert_status_not_found();
+    Ok(())
+}
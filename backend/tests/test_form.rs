@@ -1,3 +1,4 @@
+use axum::http::StatusCode;
 use axum_test::TestServer;
 use backend::dto::form::{FormRequest, FormResponse, UpdateFormRequest};
 use backend::dto::workspace::DeleteResponse;
@@ -6,7 +7,10 @@ use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
 use serde_json::json;
 use uuid::Uuid;
 mod common;
-use crate::common::helpers::{create_test_app, mock_form};
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event_with_owner, mock_form,
+    not_a_member,
+};
 
 #[tokio::test]
 async fn get_form() -> Result<()> {
@@ -15,11 +19,15 @@ async fn get_form() -> Result<()> {
     let title = "Test Form";
     let description = "Test Description";
     let mock_data = mock_form(id, event_id, title, description);
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
-        .append_query_results(vec![vec![mock_data.clone()]]);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_data.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
-    let response = server.get(format!("/form/{}", id).as_str()).await;
+    let response = server
+        .get(format!("/form/{}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
     response.assert_status_ok();
     response.assert_json(&FormResponse { form: mock_data });
     Ok(())
@@ -32,12 +40,14 @@ async fn create_form() -> Result<()> {
     let title = "Test Form";
     let description = "Test Description";
     let expected = mock_form(id, event_id, title, description);
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
         .append_query_results(vec![vec![expected.clone()]]);
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
     let response = server
         .post("/form")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(FormRequest {
             event_id,
             title: Some(title.to_string()),
@@ -54,8 +64,11 @@ async fn create_form() -> Result<()> {
 #[tokio::test]
 async fn delete_form() -> Result<()> {
     let id = Uuid::new_v4();
-    let mock_db =
-        MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(vec![MockExecResult {
+    let event_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_form(id, event_id, "Test Form", "")]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_exec_results(vec![MockExecResult {
             rows_affected: 1,
             last_insert_id: 0,
         }]);
@@ -63,6 +76,7 @@ async fn delete_form() -> Result<()> {
     let server = TestServer::new(app).unwrap();
     let response = server
         .delete(format!("/form?form_id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
         .await;
     response.assert_status_ok();
     let expected = DeleteResponse { rows_affected: 1 };
@@ -79,13 +93,15 @@ async fn update_form() -> Result<()> {
     let description = "Test Description";
     let mock_old = mock_form(id, event_id, old_title, description);
     let mock_new = mock_form(id, event_id, new_title, description);
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
         .append_query_results(vec![vec![mock_old.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
         .append_query_results(vec![vec![mock_new.clone()]]);
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
     let response = server
         .put("/form")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(UpdateFormRequest {
             id,
             title: Some(new_title.to_string()),
@@ -98,3 +114,48 @@ async fn update_form() -> Result<()> {
     response.assert_json(&FormResponse { form: mock_new });
     Ok(())
 }
+
+#[tokio::test]
+async fn delete_form_of_other_workspace_is_forbidden() -> Result<()> {
+    let id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![mock_form(id, event_id, "Foreign Form", "")]])
+            .append_query_results(vec![vec![mock_event_with_owner(event_id, "someone_else")]]),
+    );
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+    let response = server
+        .delete(format!("/form?form_id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_event_forms() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let forms = vec![
+        mock_form(Uuid::new_v4(), event_id, "Attendees", "Who is coming"),
+        mock_form(Uuid::new_v4(), event_id, "Feedback", "How was it"),
+    ];
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![forms.clone()]);
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+    let response = server
+        .get(format!("/event/{}/forms", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+    response.assert_status_ok();
+    response.assert_json(
+        &forms
+            .into_iter()
+            .map(|form| FormResponse { form })
+            .collect::<Vec<_>>(),
+    );
+    Ok(())
+}
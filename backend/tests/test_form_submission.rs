@@ -0,0 +1,279 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::dto::form_submission::{FormSubmissionRequest, FormSubmissionResponse};
+use backend::form_schema::{FieldError, validate};
+use backend::model::{event, form, form_submission};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event,
+    mock_event_with_owner, mock_form, not_a_member,
+};
+
+fn attendee_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "minLength": 1 },
+            "diet": { "type": "string", "enum": ["none", "vegetarian", "vegan"] },
+            "id_number": { "type": "string", "pattern": "^[0-9]{13}$" }
+        },
+        "required": ["name", "id_number"],
+        "additionalProperties": false
+    })
+}
+
+fn attendee_form(form_id: Uuid, event_id: Uuid) -> form::Model {
+    form::Model {
+        schema: Some(attendee_schema()),
+        ..mock_form(form_id, event_id, "Attendees", "Who is coming")
+    }
+}
+
+fn on_sale_event(id: Uuid) -> event::Model {
+    event::Model {
+        status: "on_sale".to_string(),
+        ..mock_event(id, "Concert", Uuid::new_v4())
+    }
+}
+
+fn mock_submission(form_id: Uuid, data: Value) -> form_submission::Model {
+    let now = mock_datetime();
+    form_submission::Model {
+        id: Uuid::new_v4(),
+        form_id,
+        user_id: TEST_USER_ID.to_string(),
+        reservation_id: None,
+        data,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn submit_form() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let data = json!({ "name": "Somchai", "diet": "vegan", "id_number": "1234567890123" });
+    let expected = mock_submission(form_id, data.clone());
+
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+        .append_query_results(vec![vec![on_sale_event(event_id)]])
+        .append_query_results(vec![vec![expected.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/form/{}/submissions", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(FormSubmissionRequest {
+            reservation_id: None,
+            data,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&FormSubmissionResponse::from(expected));
+    Ok(())
+}
+
+#[tokio::test]
+async fn submit_form_reports_field_errors() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+        .append_query_results(vec![vec![on_sale_event(event_id)]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/form/{}/submissions", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(FormSubmissionRequest {
+            reservation_id: None,
+            data: json!({ "name": "Somchai", "diet": "meat", "shoe_size": 42 }),
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let details = response.json::<Value>()["details"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(details.contains("`id_number` is required"));
+    assert!(details.contains("`diet` must be one of"));
+    assert!(details.contains("`shoe_size` is not a field of this form"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn submit_form_of_a_draft_event_needs_workspace_access() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let (draft, workspace) = mock_event_with_owner(event_id, "someone-else");
+
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+            .append_query_results(vec![vec![draft.clone()]])
+            .append_query_results(vec![vec![(draft, workspace)]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/form/{}/submissions", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(FormSubmissionRequest {
+            reservation_id: None,
+            data: json!({ "name": "Somchai", "id_number": "1234567890123" }),
+        }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[test]
+fn nested_field_errors_carry_their_path() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "guests": {
+                "type": "array",
+                "maxItems": 3,
+                "items": {
+                    "type": "object",
+                    "properties": { "age": { "type": "integer", "minimum": 0 } },
+                    "required": ["email"]
+                }
+            }
+        }
+    });
+    let data = json!({ "guests": [{ "email": "a@example.com", "age": 30 }, { "age": -1 }] });
+
+    assert_eq!(
+        validate(&schema, &data),
+        vec![
+            FieldError {
+                field: "guests[1].email".to_string(),
+                message: "is required".to_string(),
+            },
+            FieldError {
+                field: "guests[1].age".to_string(),
+                message: "must be at least 0".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn whole_numbers_written_as_floats_are_integers() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "guests": { "type": "integer" } }
+    });
+
+    assert!(validate(&schema, &json!({ "guests": 3.0 })).is_empty());
+    assert_eq!(validate(&schema, &json!({ "guests": 3.5 })).len(), 1);
+}
+
+#[tokio::test]
+async fn list_submissions() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let submission = mock_submission(form_id, json!({ "name": "Somchai" }));
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![submission.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/form/{}/submissions", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![FormSubmissionResponse::from(submission)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_submissions_as_csv() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let submission = mock_submission(
+        form_id,
+        json!({ "name": "Doe, Jane", "diet": "vegan", "id_number": "=1+1", "balance": -5 }),
+    );
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![submission.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/form/{}/submissions/export", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/csv; charset=utf-8");
+    assert_eq!(
+        response.text(),
+        format!(
+            "id,user_id,reservation_id,created_at,diet,id_number,name,balance\r\n\
+             {},{},,{},vegan,'=1+1,\"Doe, Jane\",-5\r\n",
+            submission.id, TEST_USER_ID, submission.created_at
+        )
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn csv_lists_answers_missing_from_the_schema_last() -> Result<()> {
+    let form_id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let submission = mock_submission(
+        form_id,
+        json!({ "name": "Somchai", "age": "42", "diet": "none", "id_number": "1234567890123" }),
+    );
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![attendee_form(form_id, event_id)]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![submission.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/form/{}/submissions/export", form_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(
+        response.text().lines().next(),
+        Some("id,user_id,reservation_id,created_at,diet,id_number,name,age")
+    );
+    Ok(())
+}
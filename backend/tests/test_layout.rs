@@ -0,0 +1,147 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::dto::layout::{
+    LayoutObject, LayoutResponse, PositionRequest, PositionResponse, SaveLayoutRequest,
+};
+use backend::model::{event_object, event_object_position};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event_object,
+    mock_event_with_owner, mock_position, mock_section,
+};
+
+#[tokio::test]
+async fn get_layout() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "10.00");
+    let placed = mock_event_object(
+        Uuid::new_v4(),
+        event_id,
+        Some(section.id),
+        "A1",
+        "available",
+    );
+    let unplaced = mock_event_object(Uuid::new_v4(), event_id, None, "A2", "available");
+    let position = mock_position(placed.id, 12.5, 40.0);
+    let rows: Vec<(event_object::Model, Option<event_object_position::Model>)> = vec![
+        (placed.clone(), Some(position.clone())),
+        (unplaced.clone(), None),
+    ];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![section.clone()]])
+        .append_query_results(vec![rows]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/layout", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&LayoutResponse {
+        event_id,
+        sections: vec![section],
+        objects: vec![
+            LayoutObject::new(placed, Some(position)),
+            LayoutObject::new(unplaced, None),
+        ],
+    });
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_layout() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let object = mock_event_object(Uuid::new_v4(), event_id, None, "A1", "available");
+    let saved = mock_position(object.id, 5.0, 7.5);
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![object.clone()]])
+        .append_query_results(vec![vec![saved.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/layout", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SaveLayoutRequest {
+            positions: vec![PositionRequest {
+                event_object_id: object.id,
+                position_x: 5.0,
+                position_y: 7.5,
+                rotation: 0.0,
+            }],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![PositionResponse::from(saved)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_layout_rejects_foreign_objects() -> Result<()> {
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![Vec::<event_object::Model>::new()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put(format!("/event/{}/layout", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SaveLayoutRequest {
+            positions: vec![PositionRequest {
+                event_object_id: Uuid::new_v4(),
+                position_x: 0.0,
+                position_y: 0.0,
+                rotation: 90.0,
+            }],
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_layout_rejects_too_many_positions() -> Result<()> {
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let positions = (0..5001)
+        .map(|_| PositionRequest {
+            event_object_id: Uuid::new_v4(),
+            position_x: 0.0,
+            position_y: 0.0,
+            rotation: 0.0,
+        })
+        .collect();
+    let response = server
+        .put(format!("/event/{}/layout", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SaveLayoutRequest { positions }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
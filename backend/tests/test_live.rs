@@ -0,0 +1,137 @@
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::live::SeatUpdate;
+use backend::dto::reservation::ReservationRequest;
+use backend::live::SeatFeed;
+use backend::model::{event, reservation, section_sale_window};
+use backend::status::SeatStatus;
+use backend::sweeper::sweep_expired_holds;
+use chrono::{Duration, Utc};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, mock_event, mock_event_object, mock_reservation,
+    mock_reservation_item, mock_section,
+};
+
+#[tokio::test]
+async fn feed_only_reaches_subscribers_of_the_event() -> Result<()> {
+    let feed = SeatFeed::default();
+    let event_id = Uuid::new_v4();
+    let seat_id = Uuid::new_v4();
+    let mut watching = feed.subscribe(event_id);
+    let mut elsewhere = feed.subscribe(Uuid::new_v4());
+
+    feed.publish(event_id, SeatStatus::Sold, vec![seat_id]);
+
+    assert_eq!(
+        watching.try_recv()?,
+        SeatUpdate {
+            event_id,
+            status: "sold".to_string(),
+            event_object_ids: vec![seat_id],
+        }
+    );
+    assert!(elsewhere.try_recv().is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn feed_forgets_an_event_when_its_last_subscriber_leaves() {
+    let feed = SeatFeed::default();
+    let event_id = Uuid::new_v4();
+    let first = feed.subscribe(event_id);
+    let second = feed.subscribe(event_id);
+
+    drop(first);
+    assert!(feed.is_watched(event_id));
+    drop(second);
+    assert!(!feed.is_watched(event_id));
+}
+
+#[tokio::test]
+async fn hold_is_published_to_the_event_feed() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let seat = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A1", "held");
+    let reservation = mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "25.00");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event::Model {
+            status: "on_sale".to_string(),
+            ..mock_event(event_id, "Concert", Uuid::new_v4())
+        }]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![vec![(seat.clone(), Some(section))]])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![mock_reservation_item(
+            reservation.id,
+            seat.id,
+            "25.00",
+        )]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let mut updates = app_state.live.subscribe(event_id);
+
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![seat.id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status_ok();
+
+    let update = updates.try_recv()?;
+    assert_eq!(update.status, "held");
+    assert_eq!(update.event_object_ids, vec![seat.id]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn expired_holds_are_published_as_available() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let seat_id = Uuid::new_v4();
+    let expired = reservation::Model {
+        expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+        ..mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "25.00")
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![expired.clone()]])
+        .append_query_results(vec![vec![mock_reservation_item(
+            expired.id, seat_id, "25.00",
+        )]])
+        .append_exec_results(vec![
+            MockExecResult {
+                rows_affected: 1,
+                last_insert_id: 0,
+            },
+            MockExecResult {
+                rows_affected: 1,
+                last_insert_id: 0,
+            },
+        ])
+        .into_connection();
+    let app_state = AppState::new(Arc::new(db));
+    let mut updates = app_state.live.subscribe(event_id);
+
+    sweep_expired_holds(&app_state).await?;
+
+    let update = updates.try_recv()?;
+    assert_eq!(update.status, "available");
+    assert_eq!(update.event_object_ids, vec![seat_id]);
+    Ok(())
+}
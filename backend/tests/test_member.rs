@@ -0,0 +1,274 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::access::WorkspaceRole;
+use backend::dto::member::{InviteMemberRequest, MemberResponse};
+use backend::dto::section::{SectionResponse, UpdateSectionRequest};
+use backend::model::{user, workspace_member};
+use chrono::Duration;
+use eyre::Result;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event_with_owner, mock_member,
+    mock_section, mock_session, mock_user, mock_workspace,
+};
+
+#[tokio::test]
+async fn invite_member() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let email = "door@example.com";
+    let expected = mock_member(workspace_id, None, email, "box_office", "pending");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test_1",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![Vec::<workspace_member::Model>::new()])
+        .append_query_results(vec![vec![expected.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/workspace/{}/members", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(InviteMemberRequest {
+            email: "Door@Example.com".to_string(),
+            role: WorkspaceRole::BoxOffice,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&MemberResponse::from(expected));
+    Ok(())
+}
+
+#[tokio::test]
+async fn invite_member_twice_conflicts() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let email = "door@example.com";
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test_1",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![mock_member(
+            workspace_id,
+            None,
+            email,
+            "viewer",
+            "pending",
+        )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .post(format!("/workspace/{}/members", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(InviteMemberRequest {
+            email: email.to_string(),
+            role: WorkspaceRole::BoxOffice,
+        }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn accept_invite() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let email = format!("{}@example.com", TEST_USER_ID);
+    let pending = mock_member(workspace_id, None, &email, "editor", "pending");
+    let accepted = workspace_member::Model {
+        user_id: Some(TEST_USER_ID.to_string()),
+        status: "active".to_string(),
+        ..pending.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending]])
+        .append_query_results(vec![vec![accepted.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/workspace/{}/members/accept", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&MemberResponse::from(accepted));
+    Ok(())
+}
+
+#[tokio::test]
+async fn unverified_email_cannot_accept_invite() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres).append_query_results(vec![vec![(
+        mock_session(TEST_USER_ID, TEST_TOKEN, Duration::hours(1)),
+        user::Model {
+            email_verified: false,
+            ..mock_user(TEST_USER_ID)
+        },
+    )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .post(format!("/workspace/{}/members/accept", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
+
+#[tokio::test]
+async fn editor_can_update_section() -> Result<()> {
+    let id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let (event, workspace) = mock_event_with_owner(event_id, "someone_else");
+    let membership = mock_member(
+        workspace.id,
+        Some(TEST_USER_ID),
+        "editor@example.com",
+        "editor",
+        "active",
+    );
+    let updated = mock_section(id, "Renamed", event_id, "20.00");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_section(id, "Floor", event_id, "20.00")]])
+        .append_query_results(vec![vec![(event, workspace)]])
+        .append_query_results(vec![vec![membership]])
+        .append_query_results(vec![vec![updated.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put("/section")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(UpdateSectionRequest {
+            id,
+            title: Some("Renamed".to_string()),
+            price: None,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&SectionResponse { section: updated });
+    Ok(())
+}
+
+#[tokio::test]
+async fn box_office_cannot_update_section() -> Result<()> {
+    let id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+    let (event, workspace) = mock_event_with_owner(event_id, "someone_else");
+    let membership = mock_member(
+        workspace.id,
+        Some(TEST_USER_ID),
+        "door@example.com",
+        "box_office",
+        "active",
+    );
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_section(id, "Floor", event_id, "20.00")]])
+        .append_query_results(vec![vec![(event, workspace)]])
+        .append_query_results(vec![vec![membership]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put("/section")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(UpdateSectionRequest {
+            id,
+            title: None,
+            price: Some(Decimal::ZERO),
+        }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn member_can_leave_workspace() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let membership = mock_member(
+        workspace_id,
+        Some(TEST_USER_ID),
+        "viewer@example.com",
+        "viewer",
+        "active",
+    );
+    let member_id = membership.id;
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![membership]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .delete(format!("/workspace/{}/members/{}", workspace_id, member_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn admin_cannot_remove_another_admin() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let other_admin = mock_member(
+        workspace_id,
+        Some("other-admin"),
+        "other@example.com",
+        "admin",
+        "active",
+    );
+    let member_id = other_admin.id;
+    let caller = mock_member(
+        workspace_id,
+        Some(TEST_USER_ID),
+        "admin@example.com",
+        "admin",
+        "active",
+    );
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![other_admin]])
+        .append_query_results(vec![vec![mock_workspace(workspace_id, "test_1", "owner")]])
+        .append_query_results(vec![vec![caller.clone()]])
+        .append_query_results(vec![vec![caller]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .delete(format!("/workspace/{}/members/{}", workspace_id, member_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::model::{payment_refund, reservation, reservation_discount};
+use backend::payment::{MockPaymentProvider, PaymentError, PaymentIntentStatus, PaymentProvider};
+use eyre::Result;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, mock_event_object, mock_reservation,
+    mock_reservation_item,
+};
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+/// Mock rows for confirming `pending`, up to and including its tickets.
+fn confirming(pending: &reservation::Model) -> MockDatabase {
+    let confirmed = reservation::Model {
+        status: "confirmed".to_string(),
+        expires_at: None,
+        ..pending.clone()
+    };
+    let item = mock_reservation_item(pending.id, Uuid::new_v4(), "25.00");
+    let seat = mock_event_object(item.event_object_id, pending.event_id, None, "A1", "sold");
+    authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![item]])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1), rows(0), rows(1)])
+        .append_query_results(vec![vec![confirmed]])
+        .append_query_results(vec![vec![seat]])
+}
+
+#[tokio::test]
+async fn confirm_captures_the_payment() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let payments = MockPaymentProvider::default();
+    let app_state = AppState::new(Arc::new(confirming(&pending).into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let intent = payments.intent_for(pending.id).unwrap();
+    assert_eq!(intent.status, PaymentIntentStatus::Captured);
+    assert_eq!(intent.amount, "25.00".parse::<Decimal>()?);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    // The intent is recorded on the hold before the buyer is charged.
+    let update = log
+        .iter()
+        .flat_map(|transaction| transaction.statements())
+        .find(|statement| {
+            statement
+                .sql
+                .starts_with(r#"UPDATE "reservation" SET "payment_provider""#)
+        })
+        .unwrap()
+        .clone();
+    let values = update.values.unwrap().0;
+    assert!(values.contains(&Value::from("mock")));
+    assert!(values.contains(&Value::from(intent.id)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn declined_payment_keeps_the_hold() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let payments = MockPaymentProvider::default();
+    payments.decline("insufficient funds");
+    let app_state = AppState::new(Arc::new(confirming(&pending).into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::PAYMENT_REQUIRED);
+    drop(server);
+
+    assert_eq!(
+        payments.intent_for(pending.id).unwrap().status,
+        PaymentIntentStatus::Failed
+    );
+    // Nothing is sold and the reservation stays pending.
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        log.iter()
+            .flat_map(|transaction| transaction.statements())
+            .all(|statement| !statement.sql.starts_with(r#"UPDATE "event_object""#))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn capture_of_a_hold_that_expired_meanwhile_is_refunded() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let expired = reservation::Model {
+        status: "expired".to_string(),
+        ..pending.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_exec_results(vec![rows(1)])
+        .append_query_results(vec![vec![expired.clone()]])
+        .append_query_results(vec![vec![expired]])
+        .append_query_results(vec![Vec::<payment_refund::Model>::new()])
+        .append_exec_results(vec![rows(1)]);
+    let payments = MockPaymentProvider::default();
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    let intent = payments.intent_for(pending.id).unwrap();
+    assert_eq!(intent.status, PaymentIntentStatus::Captured);
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].intent_id, intent.id);
+    assert_eq!(refunds[0].amount, intent.amount);
+    Ok(())
+}
+
+#[tokio::test]
+async fn capture_already_settled_by_another_confirm_is_not_refunded() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let confirmed = reservation::Model {
+        status: "confirmed".to_string(),
+        expires_at: None,
+        payment_provider: Some("mock".to_string()),
+        payment_intent_id: Some(format!("mock_pi_{}", pending.id.simple())),
+        ..pending.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_exec_results(vec![rows(1)])
+        .append_query_results(vec![vec![confirmed.clone()]])
+        .append_query_results(vec![vec![confirmed]]);
+    let payments = MockPaymentProvider::default();
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    assert_eq!(
+        payments.intent_for(pending.id).unwrap().status,
+        PaymentIntentStatus::Captured
+    );
+    assert!(payments.refunds().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn free_reservation_is_confirmed_without_payment() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "0.00",
+    );
+    let payments = MockPaymentProvider::default();
+    payments.decline("no card");
+    let app_state = AppState::new(Arc::new(confirming(&pending).into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_ok();
+    assert!(payments.intent_for(pending.id).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn intents_are_created_once_per_reservation() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let reservation_id = Uuid::new_v4();
+    let amount: Decimal = "25.00".parse()?;
+
+    let first = payments
+        .create_intent(reservation_id, amount, "THB")
+        .await?;
+    payments.capture(&first.id).await?;
+    let retried = payments
+        .create_intent(reservation_id, amount, "THB")
+        .await?;
+
+    assert_eq!(retried.id, first.id);
+    assert_eq!(
+        payments.capture(&retried.id).await?.status,
+        PaymentIntentStatus::Captured
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn refunds_cannot_exceed_the_capture() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(Uuid::new_v4(), "25.00".parse()?, "THB")
+        .await?;
+    payments.capture(&intent.id).await?;
+
+    payments.refund(&intent.id, "20.00".parse()?).await?;
+    let over = payments.refund(&intent.id, "10.00".parse()?).await;
+
+    assert!(matches!(over, Err(PaymentError::Provider(_))));
+    assert_eq!(payments.refunds().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn webhook_signatures_are_verified() {
+    let payments = MockPaymentProvider::new("secret".to_string());
+    let payload = br#"{"type":"payment.succeeded"}"#;
+    let signature = payments.sign(payload);
+
+    assert!(payments.verify_webhook(payload, &signature).is_ok());
+    assert!(matches!(
+        payments.verify_webhook(br#"{"type":"payment.failed"}"#, &signature),
+        Err(PaymentError::InvalidSignature)
+    ));
+    assert!(matches!(
+        MockPaymentProvider::default().verify_webhook(payload, &signature),
+        Err(PaymentError::InvalidSignature)
+    ));
+}
@@ -0,0 +1,500 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::payment::PaymentWebhookResponse;
+use backend::model::{payment_refund, refund, reservation};
+use backend::payment::{
+    MockPaymentProvider, PaymentIntent, PaymentIntentStatus, PaymentProvider,
+    WEBHOOK_SIGNATURE_HEADER,
+};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_USER_ID, create_test_app, mock_event_object, mock_reservation, mock_reservation_item,
+};
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+fn intent_for(reservation: &reservation::Model) -> PaymentIntent {
+    PaymentIntent {
+        id: format!("mock_pi_{}", reservation.id.simple()),
+        reservation_id: reservation.id,
+        amount: reservation.total_price,
+        currency: reservation.currency.clone(),
+        status: PaymentIntentStatus::Captured,
+    }
+}
+
+/// A pending reservation whose confirmation recorded its intent.
+fn pending() -> reservation::Model {
+    let reservation = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    reservation::Model {
+        payment_provider: Some("mock".to_string()),
+        payment_intent_id: Some(format!("mock_pi_{}", reservation.id.simple())),
+        ..reservation
+    }
+}
+
+/// Mock rows for settling `pending` as `status`, up to and including the
+/// tickets issued when it is paid.
+fn received(reservation: &reservation::Model) -> MockDatabase {
+    MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_exec_results(vec![rows(1)])
+}
+
+fn refunding(mock_db: MockDatabase) -> MockDatabase {
+    mock_db
+        .append_query_results(vec![Vec::<payment_refund::Model>::new()])
+        .append_exec_results(vec![rows(1)])
+}
+
+fn settling(pending: &reservation::Model, status: &str) -> MockDatabase {
+    settled(received(pending), pending, status)
+}
+
+fn settled(mock_db: MockDatabase, pending: &reservation::Model, status: &str) -> MockDatabase {
+    let item = mock_reservation_item(pending.id, Uuid::new_v4(), "25.00");
+    let seat = mock_event_object(item.event_object_id, pending.event_id, None, "A1", "sold");
+    mock_db
+        .append_query_results(vec![vec![item]])
+        .append_exec_results(vec![rows(1), rows(0), rows(1)])
+        .append_query_results(vec![vec![reservation::Model {
+            status: status.to_string(),
+            expires_at: None,
+            ..pending.clone()
+        }]])
+        .append_query_results(vec![vec![seat]])
+}
+
+#[tokio::test]
+async fn payment_succeeded_confirms_the_reservation() -> Result<()> {
+    let pending = pending();
+    let app_state = AppState::new(Arc::new(settling(&pending, "confirmed").into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_1", "payment.succeeded", &intent_for(&pending));
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(
+            WEBHOOK_SIGNATURE_HEADER,
+            MockPaymentProvider::default().sign(&payload),
+        )
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("confirmed".to_string()),
+        refunded: false,
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert!(statements[1].sql.contains("FOR UPDATE"));
+    assert!(
+        statements[2]
+            .sql
+            .starts_with(r#"INSERT INTO "payment_event""#)
+    );
+    assert!(statements[2].sql.contains("ON CONFLICT"));
+    assert!(
+        statements
+            .iter()
+            .any(|statement| statement.sql.starts_with(r#"UPDATE "reservation""#))
+    );
+    assert_eq!(statements.last().unwrap().sql, "COMMIT");
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_failed_releases_the_seats() -> Result<()> {
+    let pending = pending();
+    let app_state = AppState::new(Arc::new(settling(&pending, "failed").into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_2", "payment.failed", &intent_for(&pending));
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(
+            WEBHOOK_SIGNATURE_HEADER,
+            MockPaymentProvider::default().sign(&payload),
+        )
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("failed".to_string()),
+        refunded: false,
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let release = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"UPDATE "event_object""#))
+        .unwrap()
+        .clone();
+    assert!(
+        release
+            .values
+            .unwrap()
+            .0
+            .contains(&Value::from("available"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn redelivered_event_is_not_applied_again() -> Result<()> {
+    let pending = pending();
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_exec_results(vec![rows(0)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_1", "payment.succeeded", &intent_for(&pending));
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(
+            WEBHOOK_SIGNATURE_HEADER,
+            MockPaymentProvider::default().sign(&payload),
+        )
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: true,
+        reservation_status: Some("pending".to_string()),
+        refunded: false,
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        !log.last()
+            .unwrap()
+            .statements()
+            .iter()
+            .any(|statement| statement.sql.starts_with("UPDATE"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn event_for_a_settled_reservation_is_only_stored() -> Result<()> {
+    let confirmed = reservation::Model {
+        status: "confirmed".to_string(),
+        ..pending()
+    };
+    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![confirmed.clone()]])
+        .append_exec_results(vec![rows(1)]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_3", "payment.failed", &intent_for(&confirmed));
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(
+            WEBHOOK_SIGNATURE_HEADER,
+            MockPaymentProvider::default().sign(&payload),
+        )
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("confirmed".to_string()),
+        refunded: false,
+    });
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_for_an_expired_reservation_is_refunded() -> Result<()> {
+    let expired = reservation::Model {
+        status: "expired".to_string(),
+        ..pending()
+    };
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(expired.id, expired.total_price, &expired.currency)
+        .await?;
+    payments.capture(&intent.id).await?;
+    let mock_db =
+        refunding(received(&expired).append_query_results(vec![Vec::<refund::Model>::new()]));
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+    let payload = MockPaymentProvider::webhook_payload("evt_5", "payment.succeeded", &intent);
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, payments.sign(&payload))
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("expired".to_string()),
+        refunded: true,
+    });
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].intent_id, intent.id);
+    assert_eq!(refunds[0].amount, expired.total_price);
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_of_another_amount_is_refunded_and_fails_the_hold() -> Result<()> {
+    let pending = pending();
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(pending.id, "5.00".parse()?, &pending.currency)
+        .await?;
+    payments.capture(&intent.id).await?;
+    let mock_db = settled(refunding(received(&pending)), &pending, "failed");
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    let payload = MockPaymentProvider::webhook_payload("evt_6", "payment.succeeded", &intent);
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, payments.sign(&payload))
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("failed".to_string()),
+        refunded: true,
+    });
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].amount, intent.amount);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        !log.last()
+            .unwrap()
+            .statements()
+            .iter()
+            .any(|statement| statement.sql.starts_with(r#"INSERT INTO "ticket""#))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_of_another_intent_is_refunded_and_keeps_the_hold() -> Result<()> {
+    let pending = pending();
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(Uuid::new_v4(), pending.total_price, &pending.currency)
+        .await?;
+    payments.capture(&intent.id).await?;
+    let mock_db = refunding(received(&pending));
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    let payload = MockPaymentProvider::webhook_payload(
+        "evt_7",
+        "payment.succeeded",
+        &PaymentIntent {
+            reservation_id: pending.id,
+            ..intent.clone()
+        },
+    );
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, payments.sign(&payload))
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("pending".to_string()),
+        refunded: true,
+    });
+    assert_eq!(payments.refunds()[0].intent_id, intent.id);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        !log.last()
+            .unwrap()
+            .statements()
+            .iter()
+            .any(|statement| statement.sql.starts_with("UPDATE"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_refunded_before_is_not_refunded_again() -> Result<()> {
+    let expired = reservation::Model {
+        status: "expired".to_string(),
+        ..pending()
+    };
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(expired.id, expired.total_price, &expired.currency)
+        .await?;
+    payments.capture(&intent.id).await?;
+    let recorded = payment_refund::Model {
+        id: Uuid::new_v4(),
+        reservation_id: expired.id,
+        payment_provider: "mock".to_string(),
+        payment_intent_id: intent.id.clone(),
+        payment_refund_id: "mock_re_1".to_string(),
+        amount: intent.amount,
+        created_at: expired.created_at,
+        updated_at: expired.updated_at,
+    };
+    let mock_db = received(&expired)
+        .append_query_results(vec![Vec::<refund::Model>::new()])
+        .append_query_results(vec![vec![recorded]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+    let payload = MockPaymentProvider::webhook_payload("evt_8", "payment.succeeded", &intent);
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, payments.sign(&payload))
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("expired".to_string()),
+        refunded: false,
+    });
+    assert!(payments.refunds().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_the_provider_already_refunded_is_accepted() -> Result<()> {
+    let expired = reservation::Model {
+        status: "expired".to_string(),
+        ..pending()
+    };
+    let payments = MockPaymentProvider::default();
+    let intent = payments
+        .create_intent(expired.id, expired.total_price, &expired.currency)
+        .await?;
+    payments.capture(&intent.id).await?;
+    payments.refund(&intent.id, intent.amount).await?;
+    let mock_db = received(&expired)
+        .append_query_results(vec![Vec::<refund::Model>::new()])
+        .append_query_results(vec![Vec::<payment_refund::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+    let payload = MockPaymentProvider::webhook_payload("evt_9", "payment.succeeded", &intent);
+
+    let response = server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, payments.sign(&payload))
+        .bytes(payload.into())
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&PaymentWebhookResponse {
+        duplicate: false,
+        reservation_status: Some("expired".to_string()),
+        refunded: false,
+    });
+    assert_eq!(payments.refunds().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn forged_event_is_rejected() -> Result<()> {
+    let server =
+        TestServer::new(create_test_app(MockDatabase::new(DatabaseBackend::Postgres)).await?)
+            .unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_4", "payment.succeeded", &intent_for(&pending()));
+    let forged = MockPaymentProvider::new("guessed".to_string()).sign(&payload);
+
+    server
+        .post("/webhooks/payments/mock")
+        .add_header(WEBHOOK_SIGNATURE_HEADER, forged)
+        .bytes(payload.clone().into())
+        .await
+        .assert_status_unauthorized();
+    server
+        .post("/webhooks/payments/mock")
+        .bytes(payload.into())
+        .await
+        .assert_status_unauthorized();
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_provider_is_not_found() -> Result<()> {
+    let server =
+        TestServer::new(create_test_app(MockDatabase::new(DatabaseBackend::Postgres)).await?)
+            .unwrap();
+    let payload =
+        MockPaymentProvider::webhook_payload("evt_5", "payment.succeeded", &intent_for(&pending()));
+
+    server
+        .post("/webhooks/payments/stripe")
+        .add_header(
+            WEBHOOK_SIGNATURE_HEADER,
+            MockPaymentProvider::default().sign(&payload),
+        )
+        .bytes(payload.into())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    Ok(())
+}
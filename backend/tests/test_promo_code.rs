@@ -0,0 +1,394 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::promo_code::PromoCodeRequest;
+use backend::dto::reservation::{
+    ReservationDiscountResponse, ReservationRequest, ReservationResponse,
+};
+use backend::model::{
+    event, promo_code, reservation_discount, reservation_item, section, section_sale_window,
+};
+use backend::promo::{DiscountKind, discount_amount};
+use eyre::Result;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event,
+    mock_event_object, mock_reservation, mock_reservation_item, mock_section, mock_workspace,
+};
+
+fn price(price: &str) -> Decimal {
+    price.parse().unwrap()
+}
+
+fn mock_promo_code(
+    workspace_id: Uuid,
+    code: &str,
+    kind: DiscountKind,
+    amount: &str,
+) -> promo_code::Model {
+    let now = mock_datetime();
+    promo_code::Model {
+        id: Uuid::new_v4(),
+        workspace_id,
+        event_id: None,
+        section_id: None,
+        code: code.to_string(),
+        kind: kind.as_str().to_string(),
+        amount: price(amount),
+        max_uses: None,
+        max_uses_per_user: None,
+        valid_from: None,
+        valid_until: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn promo_uses(uses: i64, user_uses: i64) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("uses", Value::from(uses)),
+        ("user_uses", Value::from(user_uses)),
+    ])
+}
+
+fn request(code: &str, kind: DiscountKind, amount: &str) -> PromoCodeRequest {
+    PromoCodeRequest {
+        code: code.to_string(),
+        kind,
+        amount: price(amount),
+        event_id: None,
+        section_id: None,
+        max_uses: None,
+        max_uses_per_user: None,
+        valid_from: None,
+        valid_until: None,
+    }
+}
+
+/// Mock rows for holding one seat of `section` in an on-sale `event`, up to
+/// the promo code lookup.
+fn holding_one_seat(event: &event::Model, section: &section::Model) -> (MockDatabase, Uuid) {
+    let seat = mock_event_object(Uuid::new_v4(), event.id, Some(section.id), "A1", "held");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event.clone()]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![vec![(seat.clone(), Some(section.clone()))]])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()]);
+    (mock_db, seat.id)
+}
+
+fn on_sale_event(workspace_id: Uuid) -> event::Model {
+    event::Model {
+        status: "on_sale".to_string(),
+        ..mock_event(Uuid::new_v4(), "Concert", workspace_id)
+    }
+}
+
+#[test]
+fn discounts_never_exceed_the_seats() {
+    let workspace_id = Uuid::new_v4();
+    let percent = mock_promo_code(workspace_id, "P15", DiscountKind::Percent, "15");
+    assert_eq!(discount_amount(&percent, price("33.33")), price("5.00"));
+    let fixed = mock_promo_code(workspace_id, "F50", DiscountKind::Fixed, "50.00");
+    assert_eq!(discount_amount(&fixed, price("30.00")), price("30.00"));
+    assert_eq!(discount_amount(&fixed, price("80.00")), price("50.00"));
+}
+
+#[tokio::test]
+async fn create_promo_code_normalises_the_code() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let created = mock_promo_code(workspace_id, "SUMMER10", DiscountKind::Percent, "10");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![Vec::<promo_code::Model>::new()])
+        .append_query_results(vec![vec![created.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/workspace/{}/promo_codes", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(request(" summer10 ", DiscountKind::Percent, "10")))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&created);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = &log.last().unwrap().statements()[0];
+    assert!(insert.sql.starts_with(r#"INSERT INTO "promo_code""#));
+    assert!(
+        insert
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&Value::from("SUMMER10"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_duplicate_promo_code_conflicts() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![mock_promo_code(
+            workspace_id,
+            "SUMMER10",
+            DiscountKind::Percent,
+            "10",
+        )]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/workspace/{}/promo_codes", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(request("summer10", DiscountKind::Percent, "10")))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn percentage_over_one_hundred_is_rejected() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let mock_db =
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres)).append_query_results(vec![
+            vec![mock_workspace(workspace_id, "test", TEST_USER_ID)],
+        ]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/workspace/{}/promo_codes", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(request("ALL", DiscountKind::Percent, "150")))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn checkout_with_promo_code_records_a_discount() -> Result<()> {
+    let event = on_sale_event(Uuid::new_v4());
+    let section = mock_section(Uuid::new_v4(), "Floor", event.id, "40.00");
+    let promo = promo_code::Model {
+        max_uses: Some(100),
+        ..mock_promo_code(event.workspace_id, "SUMMER10", DiscountKind::Percent, "10")
+    };
+    let reservation = mock_reservation(Uuid::new_v4(), event.id, TEST_USER_ID, "pending", "36.00");
+    let (mock_db, seat_id) = holding_one_seat(&event, &section);
+    let items = vec![mock_reservation_item(reservation.id, seat_id, "40.00")];
+    let discount = reservation_discount::Model {
+        id: Uuid::new_v4(),
+        reservation_id: reservation.id,
+        promo_code_id: Some(promo.id),
+        code: promo.code.clone(),
+        amount: price("4.00"),
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let mock_db = mock_db
+        .append_query_results(vec![vec![promo]])
+        .append_query_results(vec![vec![promo_uses(99, 0)]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![vec![discount.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: event.id,
+            event_object_ids: vec![seat_id],
+            access_code: None,
+            promo_code: Some("summer10".to_string()),
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(
+        body.discount,
+        Some(ReservationDiscountResponse {
+            code: "SUMMER10".to_string(),
+            amount: price("4.00"),
+        })
+    );
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    let lookup = statements
+        .iter()
+        .find(|statement| statement.sql.contains(r#"FROM "promo_code""#))
+        .unwrap();
+    assert!(lookup.sql.ends_with("FOR UPDATE"));
+    let insert = statements
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "reservation" "#))
+        .unwrap();
+    assert!(
+        insert
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&Value::Decimal(Some(Box::new(price("36.00")))))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exhausted_promo_code_conflicts() -> Result<()> {
+    let event = on_sale_event(Uuid::new_v4());
+    let section = mock_section(Uuid::new_v4(), "Floor", event.id, "40.00");
+    let promo = promo_code::Model {
+        max_uses: Some(100),
+        ..mock_promo_code(event.workspace_id, "SUMMER10", DiscountKind::Percent, "10")
+    };
+    let (mock_db, seat_id) = holding_one_seat(&event, &section);
+    let mock_db = mock_db
+        .append_query_results(vec![vec![promo]])
+        .append_query_results(vec![vec![promo_uses(100, 0)]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: event.id,
+            event_object_ids: vec![seat_id],
+            access_code: None,
+            promo_code: Some("SUMMER10".to_string()),
+        }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    drop(server);
+
+    // Nothing is inserted, and the seat hold is rolled back with the rest.
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert!(
+        statements
+            .iter()
+            .all(|statement| !statement.sql.starts_with("INSERT"))
+    );
+    assert_eq!(statements.last().unwrap().sql, "ROLLBACK");
+    Ok(())
+}
+
+#[tokio::test]
+async fn promo_code_for_another_section_does_not_apply() -> Result<()> {
+    let event = on_sale_event(Uuid::new_v4());
+    let section = mock_section(Uuid::new_v4(), "Floor", event.id, "40.00");
+    let promo = promo_code::Model {
+        event_id: Some(event.id),
+        section_id: Some(Uuid::new_v4()),
+        ..mock_promo_code(event.workspace_id, "BALCONY", DiscountKind::Fixed, "5.00")
+    };
+    let (mock_db, seat_id) = holding_one_seat(&event, &section);
+    let mock_db = mock_db.append_query_results(vec![vec![promo]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: event.id,
+            event_object_ids: vec![seat_id],
+            access_code: None,
+            promo_code: Some("BALCONY".to_string()),
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_promo_code_is_rejected() -> Result<()> {
+    let event = on_sale_event(Uuid::new_v4());
+    let section = mock_section(Uuid::new_v4(), "Floor", event.id, "40.00");
+    let (mock_db, seat_id) = holding_one_seat(&event, &section);
+    let mock_db = mock_db.append_query_results(vec![Vec::<promo_code::Model>::new()]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: event.id,
+            event_object_ids: vec![seat_id],
+            access_code: None,
+            promo_code: Some("NOPE".to_string()),
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn checkout_without_promo_code_skips_the_lookup() -> Result<()> {
+    let event = on_sale_event(Uuid::new_v4());
+    let section = mock_section(Uuid::new_v4(), "Floor", event.id, "40.00");
+    let reservation = mock_reservation(Uuid::new_v4(), event.id, TEST_USER_ID, "pending", "40.00");
+    let (mock_db, seat_id) = holding_one_seat(&event, &section);
+    let mock_db = mock_db
+        .append_query_results(vec![vec![reservation]])
+        .append_query_results(vec![Vec::<reservation_item::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: event.id,
+            event_object_ids: vec![seat_id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    assert!(
+        log.last()
+            .unwrap()
+            .statements()
+            .iter()
+            .all(|statement| !statement.sql.contains("promo_code"))
+    );
+    Ok(())
+}
@@ -0,0 +1,558 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::reservation::{CancelItemsRequest, RefundResponse, ReservationResponse};
+use backend::error::AppError;
+use backend::model::{
+    check_in, event, promo_code, refund, reservation, reservation_discount, reservation_item,
+};
+use backend::payment::{MockPaymentProvider, PaymentProvider};
+use backend::promo::DiscountKind;
+use backend::refund::RefundPolicy;
+use chrono::{Duration, Utc};
+use eyre::Result;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event,
+    mock_reservation, mock_reservation_item,
+};
+
+fn price(price: &str) -> Decimal {
+    price.parse().unwrap()
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+/// A confirmed reservation of two seats paid through `payments`.
+async fn paid_reservation(
+    payments: &MockPaymentProvider,
+    total_price: &str,
+) -> Result<(reservation::Model, Vec<reservation_item::Model>)> {
+    let reservation = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "confirmed",
+        total_price,
+    );
+    let intent = payments
+        .create_intent(reservation.id, reservation.total_price, "THB")
+        .await?;
+    payments.capture(&intent.id).await?;
+    let reservation = reservation::Model {
+        expires_at: None,
+        payment_provider: Some("mock".to_string()),
+        payment_intent_id: Some(intent.id),
+        ..reservation
+    };
+    let items = vec![
+        mock_reservation_item(reservation.id, Uuid::new_v4(), "25.00"),
+        mock_reservation_item(reservation.id, Uuid::new_v4(), "25.00"),
+    ];
+    Ok((reservation, items))
+}
+
+fn event_with_fees(id: Uuid) -> event::Model {
+    event::Model {
+        settings: Some(json!({"refund": {"fee_percent": 10, "fee_per_seat": "1.50"}})),
+        ..mock_event(id, "Concert", Uuid::new_v4())
+    }
+}
+
+#[test]
+fn fees_come_off_the_booked_price() {
+    let policy = RefundPolicy::from_settings(Some(&json!({
+        "refund": {"fee_percent": "10", "fee_per_seat": 1.5}
+    })))
+    .unwrap();
+
+    assert_eq!(
+        policy.split(price("25.00")),
+        (price("21.00"), price("4.00"))
+    );
+    assert_eq!(policy.split(price("1.00")), (price("0.00"), price("1.00")));
+    assert_eq!(
+        RefundPolicy::from_settings(None)
+            .unwrap()
+            .split(price("25.00")),
+        (price("25.00"), price("0"))
+    );
+}
+
+#[test]
+fn invalid_refund_settings_are_rejected() {
+    for settings in [
+        json!({"refund": {"fee_percent": 150}}),
+        json!({"refund": {"fee_per_seat": "-1"}}),
+        json!({"refund": "none"}),
+    ] {
+        assert!(matches!(
+            RefundPolicy::from_settings(Some(&settings)),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn cancel_one_paid_seat_refunds_it_less_fees() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let updated = reservation::Model {
+        total_price: price("25.00"),
+        ..reservation.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1), rows(1)])
+        .append_query_results(vec![vec![event_with_fees(reservation.event_id)]])
+        .append_query_results(vec![Vec::<check_in::Model>::new()])
+        .append_query_results(vec![vec![updated.clone()]])
+        .append_exec_results(vec![rows(0), rows(1)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/items/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CancelItemsRequest {
+            reservation_item_ids: vec![items[0].id],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(body.status, "confirmed");
+    assert_eq!(body.total_price, price("25.00"));
+    assert!(body.items[0].cancelled_at.is_some());
+    assert!(body.items[1].cancelled_at.is_none());
+    drop(server);
+
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].amount, price("21.00"));
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    let release = statements
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"UPDATE "event_object""#))
+        .unwrap();
+    assert!(
+        release
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&Value::from("sold"))
+    );
+    assert!(statements.iter().any(|statement| {
+        statement
+            .sql
+            .starts_with(r#"UPDATE "ticket" SET "revoked_at""#)
+    }));
+    let insert = statements
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "refund""#))
+        .unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::Decimal(Some(Box::new(price("21.00"))))));
+    assert!(values.contains(&Value::Decimal(Some(Box::new(price("4.00"))))));
+    assert!(values.contains(&Value::from(refunds[0].id.clone())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_discounted_reservation_refunds_what_was_paid() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "40.00").await?;
+    let discount = reservation_discount::Model {
+        id: Uuid::new_v4(),
+        reservation_id: reservation.id,
+        promo_code_id: None,
+        code: "TEN".to_string(),
+        amount: price("10.00"),
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let cancelled = reservation::Model {
+        status: "cancelled".to_string(),
+        total_price: price("0.00"),
+        ..reservation.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![vec![discount.clone()]])
+        .append_exec_results(vec![rows(2), rows(2), rows(2)])
+        .append_query_results(vec![vec![mock_event(
+            reservation.event_id,
+            "Concert",
+            Uuid::new_v4(),
+        )]])
+        .append_query_results(vec![Vec::<check_in::Model>::new()])
+        .append_query_results(vec![vec![reservation_discount::Model {
+            amount: price("0.00"),
+            ..discount
+        }]])
+        .append_query_results(vec![vec![cancelled]])
+        .append_exec_results(vec![rows(0), rows(2)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(body.status, "cancelled");
+    assert!(body.items.iter().all(|item| item.cancelled_at.is_some()));
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].amount, price("40.00"));
+    drop(server);
+
+    // Each refund row adds up to the seat's price: 25.00 refunded for the
+    // first seat, 15.00 refunded and 10.00 of discount for the second.
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "refund""#))
+        .unwrap()
+        .clone();
+    let values = insert.values.unwrap().0;
+    for amount in ["25.00", "0.00", "15.00", "10.00"] {
+        assert!(values.contains(&Value::Decimal(Some(Box::new(price(amount))))));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_discounted_seat_takes_its_discount_along() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "45.00").await?;
+    let section_id = Uuid::new_v4();
+    let promo = promo_code::Model {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        event_id: None,
+        section_id: Some(section_id),
+        code: "VIP20".to_string(),
+        kind: DiscountKind::Percent.as_str().to_string(),
+        amount: price("20"),
+        max_uses: None,
+        max_uses_per_user: None,
+        valid_from: None,
+        valid_until: None,
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let discount = reservation_discount::Model {
+        id: Uuid::new_v4(),
+        reservation_id: reservation.id,
+        promo_code_id: Some(promo.id),
+        code: promo.code.clone(),
+        amount: price("5.00"),
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let updated = reservation::Model {
+        total_price: price("25.00"),
+        ..reservation.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![vec![discount.clone()]])
+        .append_exec_results(vec![rows(1), rows(1), rows(1)])
+        .append_query_results(vec![vec![mock_event(
+            reservation.event_id,
+            "Concert",
+            Uuid::new_v4(),
+        )]])
+        .append_query_results(vec![Vec::<check_in::Model>::new()])
+        .append_query_results(vec![vec![promo]])
+        // Only the cancelled seat was in the promo's section.
+        .append_query_results(vec![Vec::<BTreeMap<&str, Value>>::new()])
+        .append_query_results(vec![vec![reservation_discount::Model {
+            amount: price("0.00"),
+            ..discount
+        }]])
+        .append_query_results(vec![vec![updated]])
+        .append_exec_results(vec![rows(0), rows(1)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/items/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CancelItemsRequest {
+            reservation_item_ids: vec![items[0].id],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(body.total_price, price("25.00"));
+    assert_eq!(body.discount.unwrap().amount, price("0.00"));
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].amount, price("20.00"));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    let insert = statements
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "refund""#))
+        .unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    for amount in ["20.00", "0.00", "5.00"] {
+        assert!(values.contains(&Value::Decimal(Some(Box::new(price(amount))))));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_seat_of_a_hold_releases_it_without_refund() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "50.00",
+    );
+    let items = vec![
+        mock_reservation_item(pending.id, Uuid::new_v4(), "25.00"),
+        mock_reservation_item(pending.id, Uuid::new_v4(), "25.00"),
+    ];
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1)])
+        .append_query_results(vec![vec![reservation::Model {
+            total_price: price("25.00"),
+            ..pending.clone()
+        }]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/items/cancel", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CancelItemsRequest {
+            reservation_item_ids: vec![items[1].id],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(body.status, "pending");
+    assert_eq!(body.total_price, price("25.00"));
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert!(
+        !statements
+            .iter()
+            .any(|statement| statement.sql.starts_with(r#"INSERT INTO "refund""#))
+    );
+    assert_eq!(statements.last().unwrap().sql, "COMMIT");
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_seat_of_another_reservation_is_rejected() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/items/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CancelItemsRequest {
+            reservation_item_ids: vec![Uuid::new_v4()],
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    assert!(payments.refunds().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn checked_in_seat_cannot_be_cancelled() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let check_in = check_in::Model {
+        id: Uuid::new_v4(),
+        ticket_id: Uuid::new_v4(),
+        event_id: reservation.event_id,
+        gate: "North".to_string(),
+        device: None,
+        operator_id: "box-office".to_string(),
+        checked_in_at: mock_datetime(),
+        undone_at: None,
+        undone_by: None,
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_query_results(vec![vec![event_with_fees(reservation.event_id)]])
+        .append_query_results(vec![vec![check_in]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/items/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(CancelItemsRequest {
+            reservation_item_ids: vec![items[0].id],
+        }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    assert!(payments.refunds().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn seats_of_a_started_event_cannot_be_cancelled() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let started = event::Model {
+        starts_at: Some(Utc::now().naive_utc() - Duration::hours(1)),
+        ..event_with_fees(reservation.event_id)
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_query_results(vec![vec![started]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    assert!(payments.refunds().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn seats_of_a_cancelled_event_are_refunded_in_full_after_it_started() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let reservation = reservation::Model {
+        status: "refund_pending".to_string(),
+        ..reservation
+    };
+    let started = event::Model {
+        status: "cancelled".to_string(),
+        starts_at: Some(Utc::now().naive_utc() - Duration::hours(1)),
+        ..event_with_fees(reservation.event_id)
+    };
+    let cancelled = reservation::Model {
+        status: "cancelled".to_string(),
+        total_price: price("0.00"),
+        ..reservation.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_query_results(vec![vec![started]])
+        .append_query_results(vec![Vec::<check_in::Model>::new()])
+        .append_exec_results(vec![rows(2), rows(2), rows(2)])
+        .append_query_results(vec![vec![cancelled]])
+        .append_exec_results(vec![rows(0), rows(2)]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()))
+        .with_payments(Arc::new(payments.clone()));
+    let server = TestServer::new(create_router(app_state)?).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/cancel", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    let body: ReservationResponse = response.json();
+    assert_eq!(body.status, "cancelled");
+    let refunds = payments.refunds();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].amount, price("50.00"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_refunds_of_a_reservation() -> Result<()> {
+    let payments = MockPaymentProvider::default();
+    let (reservation, items) = paid_reservation(&payments, "50.00").await?;
+    let refund = refund::Model {
+        id: Uuid::new_v4(),
+        reservation_id: reservation.id,
+        reservation_item_id: items[0].id,
+        amount: price("21.00"),
+        fee: price("4.00"),
+        discount: Decimal::ZERO,
+        payment_refund_id: Some("mock_re_1".to_string()),
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![refund.clone()]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .get(format!("/reservation/{}/refunds", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![RefundResponse::from(refund)]);
+    Ok(())
+}
@@ -0,0 +1,456 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::reservation::{ReservationRequest, ReservationResponse};
+use backend::model::{
+    event, event_object, reservation, reservation_discount, reservation_item, section,
+    section_sale_window, session, user, workspace,
+};
+use backend::sweeper::sweep_expired_holds;
+use chrono::{Duration, Utc};
+use eyre::Result;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseBackend, EntityTrait, IntoActiveModel,
+    MockDatabase, MockExecResult, Value,
+};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event, mock_event_object,
+    mock_reservation, mock_reservation_item, mock_section, mock_session, mock_user, mock_workspace,
+};
+
+fn on_sale_event(event_id: Uuid) -> event::Model {
+    event::Model {
+        status: "on_sale".to_string(),
+        ..mock_event(event_id, "Concert", Uuid::new_v4())
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+#[tokio::test]
+async fn hold_seats() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let seat_a = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A1", "held");
+    let seat_b = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A2", "held");
+    let reservation = mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "50.00");
+    let items = vec![
+        mock_reservation_item(reservation.id, seat_a.id, "25.00"),
+        mock_reservation_item(reservation.id, seat_b.id, "25.00"),
+    ];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![on_sale_event(event_id)]])
+        .append_exec_results(vec![rows(2)])
+        .append_query_results(vec![vec![
+            (seat_a.clone(), Some(section.clone())),
+            (seat_b.clone(), Some(section)),
+        ]])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![items.clone()]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![seat_a.id, seat_b.id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&ReservationResponse::new(reservation, items, None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_total_is_exact() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let seats: Vec<_> = ["0.10", "0.20"]
+        .into_iter()
+        .map(|price| {
+            let section = mock_section(Uuid::new_v4(), price, event_id, price);
+            let seat = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A", "held");
+            (seat, Some(section))
+        })
+        .collect();
+    let reservation = mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "0.30");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![on_sale_event(event_id)]])
+        .append_exec_results(vec![rows(2)])
+        .append_query_results(vec![seats.clone()])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()])
+        .append_query_results(vec![vec![reservation]])
+        .append_query_results(vec![Vec::<reservation_item::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: seats.iter().map(|(seat, _)| seat.id).collect(),
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let db = Arc::into_inner(app_state.db).unwrap();
+    let insert = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|transaction| transaction.statements().to_vec())
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "reservation""#))
+        .unwrap();
+    let total: Decimal = "0.30".parse()?;
+    assert!(
+        insert
+            .values
+            .unwrap()
+            .0
+            .contains(&Value::Decimal(Some(Box::new(total))))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_taken_seat_conflicts() -> Result<()> {
+    let event_id = Uuid::new_v4();
+
+    // The conditional update only flips one of the two seats because the
+    // other was held by a concurrent request first.
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![on_sale_event(event_id)]])
+        .append_exec_results(vec![rows(1)]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![Uuid::new_v4(), Uuid::new_v4()],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_seat_without_section_conflicts() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let seat = mock_event_object(Uuid::new_v4(), event_id, None, "A1", "held");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![on_sale_event(event_id)]])
+        .append_exec_results(vec![rows(1)])
+        .append_query_results(vec![vec![(seat.clone(), None::<section::Model>)]])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![seat.id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    drop(server);
+
+    // Nothing is booked for free.
+    let db = Arc::into_inner(app_state.db).unwrap();
+    assert!(
+        db.into_transaction_log()
+            .iter()
+            .flat_map(|transaction| transaction.statements())
+            .all(|statement| !statement.sql.starts_with(r#"INSERT INTO "reservation""#))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database in TEST_DATABASE_URL"]
+async fn racing_holds_cannot_both_win() -> Result<()> {
+    let url = std::env::var("TEST_DATABASE_URL")?;
+    let schema = format!("hold_race_{}", Uuid::new_v4().simple());
+    let admin = Database::connect(&url).await?;
+    admin
+        .execute_unprepared(&format!("CREATE SCHEMA {schema}"))
+        .await?;
+    let mut options = ConnectOptions::new(&url);
+    options.set_schema_search_path(&schema).max_connections(8);
+    let db = Database::connect(options).await?;
+    Migrator::up(&db, None).await?;
+
+    let event_id = Uuid::new_v4();
+    let seat = mock_event_object(Uuid::new_v4(), event_id, None, "A1", "available");
+    let workspace = mock_workspace(Uuid::new_v4(), "Venue", TEST_USER_ID);
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    user::Entity::insert(mock_user(TEST_USER_ID).into_active_model())
+        .exec(&db)
+        .await?;
+    session::Entity::insert(
+        mock_session(TEST_USER_ID, TEST_TOKEN, Duration::hours(1)).into_active_model(),
+    )
+    .exec(&db)
+    .await?;
+    workspace::Entity::insert(workspace.clone().into_active_model())
+        .exec(&db)
+        .await?;
+    event::Entity::insert(
+        event::Model {
+            status: "on_sale".to_string(),
+            ..mock_event(event_id, "Concert", workspace.id)
+        }
+        .into_active_model(),
+    )
+    .exec(&db)
+    .await?;
+    section::Entity::insert(section.clone().into_active_model())
+        .exec(&db)
+        .await?;
+    event_object::Entity::insert(
+        event_object::Model {
+            section_id: Some(section.id),
+            ..seat.clone()
+        }
+        .into_active_model(),
+    )
+    .exec(&db)
+    .await?;
+
+    let server = Arc::new(TestServer::new(create_router(AppState::new(Arc::new(db)))?).unwrap());
+    let racers: Vec<_> = (0..8)
+        .map(|_| {
+            let server = server.clone();
+            tokio::spawn(async move {
+                server
+                    .post("/reservation")
+                    .authorization_bearer(TEST_TOKEN)
+                    .json(&json!(ReservationRequest {
+                        event_id,
+                        event_object_ids: vec![seat.id],
+                        access_code: None,
+                        promo_code: None,
+                    }))
+                    .await
+                    .status_code()
+            })
+        })
+        .collect();
+    let mut won = 0;
+    for racer in racers {
+        match racer.await? {
+            StatusCode::OK => won += 1,
+            StatusCode::CONFLICT => {}
+            status => panic!("unexpected status: {status}"),
+        }
+    }
+    drop(server);
+    admin
+        .execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await?;
+
+    assert_eq!(won, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_rejects_duplicate_seats() -> Result<()> {
+    let seat_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres));
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id: Uuid::new_v4(),
+            event_object_ids: vec![seat_id, seat_id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn confirm_reservation() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let pending = mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "25.00");
+    let confirmed = reservation::Model {
+        status: "confirmed".to_string(),
+        expires_at: None,
+        ..pending.clone()
+    };
+    let items = vec![mock_reservation_item(pending.id, Uuid::new_v4(), "25.00")];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1), rows(0), rows(1)])
+        .append_query_results(vec![vec![confirmed.clone()]])
+        .append_query_results(vec![vec![mock_event_object(
+            items[0].event_object_id,
+            event_id,
+            None,
+            "A1",
+            "sold",
+        )]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&ReservationResponse::new(confirmed, items, None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn confirm_expired_reservation_conflicts() -> Result<()> {
+    let expired = reservation::Model {
+        expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+        ..mock_reservation(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            TEST_USER_ID,
+            "pending",
+            "25.00",
+        )
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![expired.clone()]])
+        .append_query_results(vec![vec![expired.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/confirm", expired.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancel_reservation() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let pending = mock_reservation(Uuid::new_v4(), event_id, TEST_USER_ID, "pending", "25.00");
+    let cancelled = reservation::Model {
+        status: "cancelled".to_string(),
+        expires_at: None,
+        ..pending.clone()
+    };
+    let items = vec![mock_reservation_item(pending.id, Uuid::new_v4(), "25.00")];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![items.clone()])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1)])
+        .append_query_results(vec![vec![cancelled.clone()]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post(format!("/reservation/{}/cancel", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&ReservationResponse::new(cancelled, items, None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn sweeper_releases_expired_holds() -> Result<()> {
+    let expired = reservation::Model {
+        expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+        ..mock_reservation(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            TEST_USER_ID,
+            "pending",
+            "50.00",
+        )
+    };
+    let items = vec![
+        mock_reservation_item(expired.id, Uuid::new_v4(), "25.00"),
+        mock_reservation_item(expired.id, Uuid::new_v4(), "25.00"),
+    ];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![expired]])
+        .append_query_results(vec![items])
+        .append_exec_results(vec![rows(2), rows(1)])
+        .into_connection();
+    let app_state = AppState::new(Arc::new(db));
+
+    assert_eq!(sweep_expired_holds(&app_state).await?, 2);
+
+    let db = Arc::into_inner(app_state.db).unwrap();
+    let log = db.into_transaction_log();
+    let statements = log[0].statements();
+    assert!(statements[1].sql.ends_with("FOR UPDATE SKIP LOCKED"));
+    assert!(statements[4].sql.starts_with(r#"UPDATE "reservation""#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn sweeper_without_expired_holds_is_a_no_op() -> Result<()> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<reservation::Model>::new()])
+        .into_connection();
+    let app_state = AppState::new(Arc::new(db));
+
+    assert_eq!(sweep_expired_holds(&app_state).await?, 0);
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::reservation::ReservationRequest;
+use backend::dto::section::SaleWindowRequest;
+use backend::error::AppError;
+use backend::model::{event, reservation_item, section_sale_window};
+use backend::sale_window::booking_price;
+use chrono::{Duration, Utc};
+use eyre::Result;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event,
+    mock_event_object, mock_event_with_owner, mock_reservation, mock_sale_window, mock_section,
+};
+
+fn price(price: &str) -> Decimal {
+    price.parse().unwrap()
+}
+
+/// A presale behind `FANS` running alongside general sale.
+fn presale_and_general(section_id: Uuid) -> Vec<section_sale_window::Model> {
+    let now = mock_datetime();
+    vec![
+        mock_sale_window(
+            section_id,
+            "Presale",
+            "20.00",
+            Some(now - Duration::days(1)),
+            Some(now + Duration::days(1)),
+            Some("FANS"),
+        ),
+        mock_sale_window(
+            section_id,
+            "General sale",
+            "30.00",
+            Some(now - Duration::hours(1)),
+            None,
+            None,
+        ),
+    ]
+}
+
+#[test]
+fn section_without_windows_sells_at_its_base_price() {
+    let section = mock_section(Uuid::new_v4(), "Floor", Uuid::new_v4(), "25.00");
+    let booked = booking_price(&section, &[], mock_datetime(), None);
+    assert_eq!(booked.ok(), Some(price("25.00")));
+}
+
+#[test]
+fn access_code_unlocks_the_presale_price() {
+    let section = mock_section(Uuid::new_v4(), "Floor", Uuid::new_v4(), "25.00");
+    let windows = presale_and_general(section.id);
+
+    let with_code = booking_price(&section, &windows, mock_datetime(), Some(" FANS "));
+    assert_eq!(with_code.ok(), Some(price("20.00")));
+    let without_code = booking_price(&section, &windows, mock_datetime(), None);
+    assert_eq!(without_code.ok(), Some(price("30.00")));
+}
+
+#[test]
+fn presale_only_section_needs_the_code() {
+    let section = mock_section(Uuid::new_v4(), "Floor", Uuid::new_v4(), "25.00");
+    let windows = presale_and_general(section.id);
+    let before_general_sale = mock_datetime() - Duration::hours(2);
+
+    let booked = booking_price(&section, &windows, before_general_sale, Some("WRONG"));
+    assert!(matches!(booked, Err(AppError::Forbidden)));
+}
+
+#[test]
+fn section_outside_its_windows_is_not_on_sale() {
+    let section = mock_section(Uuid::new_v4(), "Floor", Uuid::new_v4(), "25.00");
+    let windows = presale_and_general(section.id);
+    let before_presale = mock_datetime() - Duration::days(2);
+
+    let booked = booking_price(&section, &windows, before_presale, Some("FANS"));
+    assert!(matches!(booked, Err(AppError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn replace_section_windows() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let windows = presale_and_general(section.id);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![section.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![windows.clone()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .put(format!("/section/{}/windows", section.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(
+            windows
+                .iter()
+                .map(|window| SaleWindowRequest {
+                    name: window.name.clone(),
+                    price: window.price,
+                    opens_at: window.opens_at.map(|at| at.and_utc().fixed_offset()),
+                    closes_at: window.closes_at.map(|at| at.and_utc().fixed_offset()),
+                    access_code: window.access_code.clone(),
+                })
+                .collect::<Vec<_>>()
+        ))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&windows);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statements = log.last().unwrap().statements();
+    assert!(
+        statements[1]
+            .sql
+            .starts_with(r#"DELETE FROM "section_sale_window""#)
+    );
+    assert!(
+        statements[2]
+            .sql
+            .starts_with(r#"INSERT INTO "section_sale_window""#)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn window_closing_before_it_opens_is_rejected() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![section.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+    let opens_at = Utc::now().fixed_offset();
+
+    server
+        .put(format!("/section/{}/windows", section.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!([SaleWindowRequest {
+            name: "Presale".to_string(),
+            price: price("20.00"),
+            opens_at: Some(opens_at),
+            closes_at: Some(opens_at - Duration::hours(1)),
+            access_code: None,
+        }]))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_with_access_code_books_at_the_presale_price() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let seat = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A1", "held");
+    let now = Utc::now().naive_utc();
+    let presale = mock_sale_window(
+        section.id,
+        "Presale",
+        "20.00",
+        Some(now - Duration::days(1)),
+        Some(now + Duration::days(1)),
+        Some("FANS"),
+    );
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event::Model {
+            status: "on_sale".to_string(),
+            ..mock_event(event_id, "Concert", Uuid::new_v4())
+        }]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![vec![(seat.clone(), Some(section))]])
+        .append_query_results(vec![vec![presale]])
+        .append_query_results(vec![vec![mock_reservation(
+            Uuid::new_v4(),
+            event_id,
+            TEST_USER_ID,
+            "pending",
+            "20.00",
+        )]])
+        .append_query_results(vec![Vec::<reservation_item::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![seat.id],
+            access_code: Some("FANS".to_string()),
+            promo_code: None,
+        }))
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let item = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| {
+            statement
+                .sql
+                .starts_with(r#"INSERT INTO "reservation_item""#)
+        })
+        .unwrap()
+        .clone();
+    assert!(
+        item.values
+            .unwrap()
+            .0
+            .contains(&Value::Decimal(Some(Box::new(price("20.00")))))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn hold_without_access_code_during_presale_is_forbidden() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let section = mock_section(Uuid::new_v4(), "Floor", event_id, "25.00");
+    let seat = mock_event_object(Uuid::new_v4(), event_id, Some(section.id), "A1", "held");
+    let now = Utc::now().naive_utc();
+    let presale = mock_sale_window(
+        section.id,
+        "Presale",
+        "20.00",
+        Some(now - Duration::days(1)),
+        None,
+        Some("FANS"),
+    );
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![event::Model {
+            status: "on_sale".to_string(),
+            ..mock_event(event_id, "Concert", Uuid::new_v4())
+        }]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![vec![(seat.clone(), Some(section))]])
+        .append_query_results(vec![vec![presale]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post("/reservation")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(ReservationRequest {
+            event_id,
+            event_object_ids: vec![seat.id],
+            access_code: None,
+            promo_code: None,
+        }))
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
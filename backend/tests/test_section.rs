@@ -1,30 +1,42 @@
+use axum::http::StatusCode;
 use axum_test::TestServer;
-use backend::dto::section::{SectionRequest, SectionResponse, UpdateSectionRequest};
+use backend::dto::section::{
+    SeatCounts, SectionRequest, SectionResponse, SectionSeatsResponse, UpdateSectionRequest,
+};
 use backend::dto::workspace::DeleteResponse;
 use eyre::Result;
-use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use sea_orm::prelude::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
 use serde_json::json;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 mod common;
-use crate::common::helpers::{create_test_app, mock_section};
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_event_with_owner, mock_section,
+    not_a_member,
+};
 
 #[tokio::test]
 async fn get_section() -> Result<()> {
     let id = Uuid::new_v4();
     let event_id = Uuid::new_v4();
     let title = "Test Section";
-    let price = 100.0;
+    let price = "100.00";
 
     let mock_data = mock_section(id, title, event_id, price);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
-        .append_query_results(vec![vec![mock_data.clone()]]);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_data.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
 
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
 
-    let response = server.get(format!("/section/{}", id).as_str()).await;
+    let response = server
+        .get(format!("/section/{}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
 
     response.assert_status_ok();
     response.assert_json(&SectionResponse { section: mock_data });
@@ -36,11 +48,12 @@ async fn create_section() -> Result<()> {
     let id = Uuid::new_v4();
     let event_id = Uuid::new_v4();
     let title = "Test Section";
-    let price = 100.0;
+    let price = "100.00";
 
     let expected = mock_section(id, title, event_id, price);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
         .append_query_results(vec![vec![expected.clone()]]);
 
     let app = create_test_app(mock_db).await?;
@@ -48,10 +61,11 @@ async fn create_section() -> Result<()> {
 
     let response = server
         .post("/section")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(SectionRequest {
             event_id,
             title: title.to_string(),
-            price,
+            price: price.parse()?,
         }))
         .await;
 
@@ -63,9 +77,17 @@ async fn create_section() -> Result<()> {
 #[tokio::test]
 async fn delete_section() -> Result<()> {
     let id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
 
-    let mock_db =
-        MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(vec![MockExecResult {
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_section(
+            id,
+            "Test Section",
+            event_id,
+            "100.00",
+        )]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_exec_results(vec![MockExecResult {
             rows_affected: 1,
             last_insert_id: 0,
         }]);
@@ -73,7 +95,10 @@ async fn delete_section() -> Result<()> {
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
 
-    let response = server.delete(format!("/section?id={}", id).as_str()).await;
+    let response = server
+        .delete(format!("/section?id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
 
     response.assert_status_ok();
     let expected = DeleteResponse { rows_affected: 1 };
@@ -87,13 +112,14 @@ async fn update_section() -> Result<()> {
     let event_id = Uuid::new_v4();
     let old_title = "Old Section";
     let new_title = "Updated Section";
-    let price = 50.0;
+    let price = "50.00";
 
     let mock_old = mock_section(id, old_title, event_id, price);
     let mock_new = mock_section(id, new_title, event_id, price);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
         .append_query_results(vec![vec![mock_old.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
         .append_query_results(vec![vec![mock_new.clone()]]);
 
     let app = create_test_app(mock_db).await?;
@@ -101,10 +127,11 @@ async fn update_section() -> Result<()> {
 
     let response = server
         .put("/section")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(UpdateSectionRequest {
             id,
             title: Some(new_title.to_string()),
-            price: Some(price),
+            price: Some(price.parse()?),
         }))
         .await;
 
@@ -112,3 +139,126 @@ async fn update_section() -> Result<()> {
     response.assert_json(&SectionResponse { section: mock_new });
     Ok(())
 }
+
+#[tokio::test]
+async fn update_section_of_other_workspace_is_forbidden() -> Result<()> {
+    let id = Uuid::new_v4();
+    let event_id = Uuid::new_v4();
+
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![mock_section(
+                id,
+                "Foreign Section",
+                event_id,
+                "10.00",
+            )]])
+            .append_query_results(vec![vec![mock_event_with_owner(event_id, "someone_else")]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .put("/section")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(UpdateSectionRequest {
+            id,
+            title: Some("Hijacked".to_string()),
+            price: Some(Decimal::ZERO),
+        }))
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_section_rejects_sub_cent_price() -> Result<()> {
+    let event_id = Uuid::new_v4();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/section")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!({ "event_id": event_id, "title": "Floor", "price": "10.005" }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+fn seat_count(section_id: Uuid, status: &str, seats: i64) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("section_id", Value::from(section_id)),
+        ("status", Value::from(status)),
+        ("seats", Value::from(seats)),
+    ])
+}
+
+#[tokio::test]
+async fn list_event_sections_with_seat_counts() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let floor = mock_section(Uuid::new_v4(), "Floor", event_id, "100.00");
+    let balcony = mock_section(Uuid::new_v4(), "Balcony", event_id, "50.00");
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![vec![floor.clone(), balcony.clone()]])
+        .append_query_results(vec![vec![
+            seat_count(floor.id, "available", 7),
+            seat_count(floor.id, "held", 2),
+            seat_count(floor.id, "sold", 1),
+        ]]);
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/sections", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![
+        SectionSeatsResponse {
+            section: floor,
+            seats: SeatCounts {
+                total: 10,
+                available: 7,
+                held: 2,
+                sold: 1,
+            },
+        },
+        SectionSeatsResponse {
+            section: balcony,
+            seats: SeatCounts::default(),
+        },
+    ]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_event_sections_requires_membership() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![mock_event_with_owner(event_id, "someone-else")]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get(format!("/event/{}/sections", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
@@ -0,0 +1,379 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::event::{EventResponse, UpdateEventRequest};
+use backend::dto::series::{SeriesRequest, SeriesResponse, UpdateSeriesRequest};
+use backend::model::{
+    event, event_object, event_object_position, event_series, form, section, section_sale_window,
+    workspace,
+};
+use backend::recurrence::{Frequency, Recurrence};
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event_with_owner,
+    mock_workspace,
+};
+
+fn at(date: &str) -> NaiveDateTime {
+    format!("{}T19:30:00", date).parse().unwrap()
+}
+
+fn weekly(count: u32) -> Recurrence {
+    Recurrence {
+        starts_at: at("2025-01-03"),
+        frequency: Frequency::Weekly,
+        interval: 1,
+        count: Some(count),
+        until: None,
+        exclusions: vec![],
+    }
+}
+
+fn mock_series(id: Uuid, workspace_id: Uuid) -> event_series::Model {
+    let now = mock_datetime();
+    event_series::Model {
+        id,
+        workspace_id,
+        template_event_id: None,
+        title: "Friday Jazz".to_string(),
+        starts_at: at("2025-01-03"),
+        frequency: "weekly".to_string(),
+        interval: 1,
+        count: Some(2),
+        until: None,
+        exclusions: json!([]),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn occurrence(series_id: Uuid, workspace_id: Uuid, starts_at: NaiveDateTime) -> event::Model {
+    let now = mock_datetime();
+    event::Model {
+        id: Uuid::new_v4(),
+        title: "Friday Jazz".to_string(),
+        workspace_id,
+        description: None,
+        settings: None,
+        starts_at: Some(starts_at),
+        ends_at: Some(starts_at + Duration::hours(2)),
+        currency: "THB".to_string(),
+        timezone: "UTC".to_string(),
+        series_id: Some(series_id),
+        series_detached: false,
+        status: "draft".to_string(),
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
+
+#[test]
+fn weekly_occurrences_skip_exclusions() -> Result<()> {
+    let recurrence = Recurrence {
+        exclusions: vec![NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()],
+        ..weekly(3)
+    };
+
+    // The excluded week still counts towards `count`, as with RRULE EXDATE.
+    assert_eq!(
+        recurrence.occurrences()?,
+        vec![at("2025-01-03"), at("2025-01-17")]
+    );
+    Ok(())
+}
+
+#[test]
+fn monthly_occurrences_skip_short_months() -> Result<()> {
+    let recurrence = Recurrence {
+        starts_at: at("2025-01-31"),
+        frequency: Frequency::Monthly,
+        count: None,
+        until: Some(at("2025-05-31")),
+        ..weekly(0)
+    };
+
+    assert_eq!(
+        recurrence.occurrences()?,
+        vec![at("2025-01-31"), at("2025-03-31"), at("2025-05-31")]
+    );
+    Ok(())
+}
+
+#[test]
+fn unbounded_or_oversized_rules_are_rejected() {
+    let unbounded = Recurrence {
+        count: None,
+        ..weekly(0)
+    };
+    assert!(unbounded.occurrences().is_err());
+
+    let daily_for_years = Recurrence {
+        frequency: Frequency::Daily,
+        count: None,
+        until: Some(at("2030-01-01")),
+        ..weekly(0)
+    };
+    assert!(daily_for_years.occurrences().is_err());
+}
+
+#[tokio::test]
+async fn create_series_copies_the_template_per_occurrence() -> Result<()> {
+    let template_id = Uuid::new_v4();
+    let (template, workspace) = mock_event_with_owner(template_id, TEST_USER_ID);
+    let template = event::Model {
+        title: "Friday Jazz".to_string(),
+        starts_at: Some(at("2024-12-27")),
+        ends_at: Some(at("2024-12-27") + Duration::hours(2)),
+        ..template
+    };
+    let series = mock_series(Uuid::new_v4(), template.workspace_id);
+    let occurrences = vec![
+        occurrence(series.id, template.workspace_id, at("2025-01-03")),
+        occurrence(series.id, template.workspace_id, at("2025-01-10")),
+    ];
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(template, workspace)]])
+        .append_query_results(vec![vec![series.clone()]])
+        .append_query_results(vec![Vec::<section::Model>::new()])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()])
+        .append_query_results(vec![Vec::<event_object::Model>::new()])
+        .append_query_results(vec![Vec::<event_object_position::Model>::new()])
+        .append_query_results(vec![Vec::<form::Model>::new()])
+        .append_query_results(vec![vec![occurrences[0].clone()]])
+        .append_query_results(vec![vec![occurrences[1].clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .post("/series")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SeriesRequest {
+            template_event_id: template_id,
+            title: None,
+            starts_at: at("2025-01-03").and_utc().fixed_offset(),
+            frequency: Frequency::Weekly,
+            interval: None,
+            count: Some(2),
+            until: None,
+            exclusions: None,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&SeriesResponse {
+        series,
+        occurrences: occurrences.into_iter().map(EventResponse::from).collect(),
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let event_inserts: Vec<_> = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .filter(|statement| statement.sql.starts_with(r#"INSERT INTO "event" "#))
+        .map(|statement| statement.values.clone().unwrap().0)
+        .collect();
+    assert_eq!(event_inserts.len(), 2);
+    // Each occurrence lasts as long as the template.
+    assert!(
+        event_inserts[1].contains(&sea_orm::Value::from(at("2025-01-10") + Duration::hours(2)))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_series_stores_until_in_utc() -> Result<()> {
+    let template_id = Uuid::new_v4();
+    let (template, workspace) = mock_event_with_owner(template_id, TEST_USER_ID);
+    let template = event::Model {
+        timezone: "Asia/Bangkok".to_string(),
+        ..template
+    };
+    let series = mock_series(Uuid::new_v4(), template.workspace_id);
+    let bangkok = FixedOffset::east_opt(7 * 3600).unwrap();
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(template, workspace)]])
+        .append_query_results(vec![vec![series.clone()]])
+        .append_query_results(vec![Vec::<section::Model>::new()])
+        .append_query_results(vec![Vec::<section_sale_window::Model>::new()])
+        .append_query_results(vec![Vec::<event_object::Model>::new()])
+        .append_query_results(vec![Vec::<event_object_position::Model>::new()])
+        .append_query_results(vec![Vec::<form::Model>::new()])
+        .append_query_results(vec![vec![occurrence(
+            series.id,
+            series.workspace_id,
+            at("2025-01-03"),
+        )]])
+        .append_query_results(vec![vec![occurrence(
+            series.id,
+            series.workspace_id,
+            at("2025-01-10"),
+        )]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post("/series")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SeriesRequest {
+            template_event_id: template_id,
+            title: None,
+            starts_at: bangkok.from_local_datetime(&at("2025-01-03")).unwrap(),
+            frequency: Frequency::Weekly,
+            interval: None,
+            count: None,
+            until: Some(bangkok.from_local_datetime(&at("2025-01-10")).unwrap()),
+            exclusions: None,
+        }))
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "event_series""#))
+        .unwrap()
+        .values
+        .clone()
+        .unwrap()
+        .0;
+    // Both `starts_at` and `until` are stored in UTC, not in Bangkok time.
+    let utc = |local: NaiveDateTime| sea_orm::Value::from(Some(local - Duration::hours(7)));
+    assert!(insert.contains(&utc(at("2025-01-03"))));
+    assert!(insert.contains(&utc(at("2025-01-10"))));
+    assert!(!insert.contains(&sea_orm::Value::from(Some(at("2025-01-10")))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_series_skips_detached_occurrences() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let series = mock_series(Uuid::new_v4(), workspace_id);
+    let renamed = event_series::Model {
+        title: "Saturday Jazz".to_string(),
+        ..series.clone()
+    };
+    let workspace: workspace::Model = mock_workspace(workspace_id, "test", TEST_USER_ID);
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(series.clone(), workspace)]])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 2,
+            last_insert_id: 0,
+        }])
+        .append_query_results(vec![vec![renamed.clone()]])
+        .append_query_results(vec![Vec::<event::Model>::new()]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .put(format!("/series/{}", series.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(UpdateSeriesRequest {
+            title: Some("Saturday Jazz".to_string()),
+            ..Default::default()
+        }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<SeriesResponse>().series, renamed);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let update = &log.last().unwrap().statements()[1];
+    assert!(update.sql.starts_with(r#"UPDATE "event""#));
+    assert!(update.sql.contains(r#""event"."series_detached" = $"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn editing_one_occurrence_detaches_it() -> Result<()> {
+    let series_id = Uuid::new_v4();
+    let (event, workspace) = mock_event_with_owner(Uuid::new_v4(), TEST_USER_ID);
+    let event = event::Model {
+        series_id: Some(series_id),
+        ..event
+    };
+    let edited = event::Model {
+        title: "Jazz Special".to_string(),
+        series_detached: true,
+        ..event.clone()
+    };
+
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event.clone(), workspace)]])
+        .append_query_results(vec![vec![edited.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .put("/event")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(UpdateEventRequest {
+            id: event.id,
+            title: Some("Jazz Special".to_string()),
+            description: None,
+            starts_at: None,
+            ends_at: None,
+            timezone: None,
+            currency: None,
+            settings: None,
+        }))
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let update = &log.last().unwrap().statements()[0];
+    assert!(update.sql.contains(r#""series_detached" = $"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_series_rejects_an_unbounded_rule() -> Result<()> {
+    let template_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(template_id, TEST_USER_ID)]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .post("/series")
+        .authorization_bearer(TEST_TOKEN)
+        .json(&json!(SeriesRequest {
+            template_event_id: template_id,
+            title: None,
+            starts_at: at("2025-01-03").and_utc().fixed_offset(),
+            frequency: Frequency::Daily,
+            interval: None,
+            count: None,
+            until: None,
+            exclusions: None,
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use axum::http::header;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::ticket::TicketResponse;
+use backend::model::{reservation, reservation_discount, ticket, workspace_member};
+use backend::ticket::{TicketClaims, TicketSigner};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, create_test_app, mock_datetime, mock_event_object,
+    mock_event_with_owner, mock_reservation, mock_reservation_item,
+};
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        rows_affected,
+        last_insert_id: 0,
+    }
+}
+
+fn claims() -> TicketClaims {
+    TicketClaims {
+        ticket_id: Uuid::new_v4(),
+        event_id: Uuid::new_v4(),
+        seat_label: "A1".to_string(),
+    }
+}
+
+/// A ticket of a confirmed reservation owned by the test user.
+fn mock_ticket() -> (reservation::Model, ticket::Model) {
+    let reservation = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "confirmed",
+        "25.00",
+    );
+    let claims = TicketClaims {
+        event_id: reservation.event_id,
+        ..claims()
+    };
+    let ticket = ticket::Model {
+        id: claims.ticket_id,
+        reservation_id: reservation.id,
+        reservation_item_id: Uuid::new_v4(),
+        event_id: reservation.event_id,
+        seat_label: claims.seat_label.clone(),
+        token: TicketSigner::development().sign(&claims),
+        revoked_at: None,
+        created_at: mock_datetime(),
+        updated_at: mock_datetime(),
+    };
+    (reservation, ticket)
+}
+
+#[test]
+fn signed_tokens_verify_with_the_same_key_only() {
+    let signer = TicketSigner::new(b"secret");
+    let claims = claims();
+    let token = signer.sign(&claims);
+
+    assert_eq!(signer.verify(&token), Some(claims));
+    assert_eq!(TicketSigner::new(b"other").verify(&token), None);
+
+    let (payload, signature) = token.split_once('.').unwrap();
+    let forged = format!("{}A.{}", payload, signature);
+    assert_eq!(signer.verify(&forged), None);
+    assert_eq!(signer.verify("not a ticket"), None);
+}
+
+#[tokio::test]
+async fn confirm_issues_a_ticket_per_seat() -> Result<()> {
+    let pending = mock_reservation(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        TEST_USER_ID,
+        "pending",
+        "25.00",
+    );
+    let item = mock_reservation_item(pending.id, Uuid::new_v4(), "25.00");
+    let seat = mock_event_object(item.event_object_id, pending.event_id, None, "B7", "sold");
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![pending.clone()]])
+        .append_query_results(vec![vec![item.clone()]])
+        .append_query_results(vec![Vec::<reservation_discount::Model>::new()])
+        .append_exec_results(vec![rows(1), rows(1), rows(0), rows(1)])
+        .append_query_results(vec![vec![reservation::Model {
+            status: "confirmed".to_string(),
+            expires_at: None,
+            ..pending.clone()
+        }]])
+        .append_query_results(vec![vec![seat]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .post(format!("/reservation/{}/confirm", pending.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_ok();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let insert = log
+        .last()
+        .unwrap()
+        .statements()
+        .iter()
+        .find(|statement| statement.sql.starts_with(r#"INSERT INTO "ticket""#))
+        .unwrap()
+        .clone();
+    let values = insert.values.unwrap().0;
+    assert!(values.contains(&Value::from(item.id)));
+    let claims = values
+        .iter()
+        .find_map(|value| match value {
+            Value::String(Some(token)) => TicketSigner::development().verify(token),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(claims.event_id, pending.event_id);
+    assert_eq!(claims.seat_label, "B7");
+    assert!(values.contains(&Value::from(claims.ticket_id)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_tickets_of_a_reservation() -> Result<()> {
+    let (reservation, ticket) = mock_ticket();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![ticket.clone()]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .get(format!("/reservation/{}/tickets", reservation.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![TicketResponse::from(ticket)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticket_qr_renders_as_svg_by_default() -> Result<()> {
+    let (reservation, ticket) = mock_ticket();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![ticket.clone()]])
+        .append_query_results(vec![vec![reservation]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .get(format!("/ticket/{}/qr", ticket.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_header(header::CONTENT_TYPE, "image/svg+xml");
+    assert!(response.text().contains("<svg"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticket_qr_renders_as_png() -> Result<()> {
+    let (reservation, ticket) = mock_ticket();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![ticket.clone()]])
+        .append_query_results(vec![vec![reservation]]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    let response = server
+        .get(format!("/ticket/{}/qr", ticket.id).as_str())
+        .add_query_param("format", "png")
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_header(header::CONTENT_TYPE, "image/png");
+    assert!(response.as_bytes().starts_with(b"\x89PNG"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticket_of_someone_else_is_not_rendered() -> Result<()> {
+    let (reservation, ticket) = mock_ticket();
+    let reservation = reservation::Model {
+        user_id: "someone-else".to_string(),
+        ..reservation
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![ticket.clone()]])
+        .append_query_results(vec![vec![reservation.clone()]])
+        .append_query_results(vec![vec![mock_event_with_owner(
+            reservation.event_id,
+            "someone-else",
+        )]])
+        .append_query_results(vec![Vec::<workspace_member::Model>::new()]);
+    let server = TestServer::new(create_test_app(mock_db).await?).unwrap();
+
+    server
+        .get(format!("/ticket/{}/qr", ticket.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
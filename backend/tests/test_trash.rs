@@ -0,0 +1,324 @@
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use backend::app::{AppState, create_router};
+use backend::dto::event::EventResponse;
+use backend::dto::trash::TrashResponse;
+use backend::dto::workspace::{DeleteResponse, WorkspaceResponse};
+use backend::model::{event, form, reservation, section, workspace};
+use backend::trash::{TRASH_RETENTION_DAYS, purge_trash};
+use chrono::{Duration, NaiveDateTime};
+use eyre::Result;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+
+use crate::common::helpers::{
+    TEST_TOKEN, TEST_USER_ID, authenticated, mock_datetime, mock_event, mock_event_with_owner,
+    mock_form, mock_reservation, mock_section, mock_workspace, not_a_member,
+};
+
+fn deleted() -> Option<NaiveDateTime> {
+    Some(mock_datetime() + Duration::hours(1))
+}
+
+#[tokio::test]
+async fn delete_event_moves_it_to_the_trash() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_event_with_owner(event_id, TEST_USER_ID)]])
+        .append_query_results(vec![Vec::<reservation::Model>::new()])
+        .append_exec_results(vec![MockExecResult {
+            rows_affected: 1,
+            last_insert_id: 0,
+        }]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .delete(format!("/event?event_id={}", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&DeleteResponse { rows_affected: 1 });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let statement = &log.last().unwrap().statements()[0];
+    assert!(
+        statement
+            .sql
+            .starts_with(r#"UPDATE "event" SET "deleted_at" = $1"#)
+    );
+    assert!(statement.sql.contains(r#""event"."deleted_at" IS NULL"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn closed_event_with_paid_reservations_cannot_be_deleted() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let (event, workspace) = mock_event_with_owner(event_id, TEST_USER_ID);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(
+            event::Model {
+                status: "closed".to_string(),
+                ..event
+            },
+            workspace,
+        )]])
+        .append_query_results(vec![vec![mock_reservation(
+            Uuid::new_v4(),
+            event_id,
+            TEST_USER_ID,
+            "refund_pending",
+            "25.00",
+        )]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .delete(format!("/event?event_id={}", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let lookup = &log.last().unwrap().statements()[0];
+    assert!(lookup.sql.contains(r#""reservation"."status" IN ($2, $3)"#));
+    assert!(
+        lookup
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&Value::from("refund_pending"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn event_of_deleted_workspace_is_not_found() -> Result<()> {
+    let event_id = Uuid::new_v4();
+    let (event, workspace) = mock_event_with_owner(event_id, TEST_USER_ID);
+    let workspace = workspace::Model {
+        deleted_at: deleted(),
+        ..workspace
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![(event, workspace)]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    server
+        .get(format!("/event/{}", event_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_not_found();
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    let lookup = &log.last().unwrap().statements()[0];
+    assert!(lookup.sql.contains(r#""event"."deleted_at" IS NULL"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn restore_event() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let trashed = event::Model {
+        deleted_at: deleted(),
+        ..mock_event(Uuid::new_v4(), "Concert", workspace_id)
+    };
+    let restored = event::Model {
+        deleted_at: None,
+        ..trashed.clone()
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![trashed.clone()]])
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![restored.clone()]]);
+    let server = TestServer::new(create_router(AppState::new(Arc::new(
+        mock_db.into_connection(),
+    )))?)
+    .unwrap();
+
+    let response = server
+        .post(format!("/event/{}/restore", trashed.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&EventResponse::from(restored));
+    Ok(())
+}
+
+#[tokio::test]
+async fn restore_event_not_in_trash_is_not_found() -> Result<()> {
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![Vec::<event::Model>::new()]);
+    let server = TestServer::new(create_router(AppState::new(Arc::new(
+        mock_db.into_connection(),
+    )))?)
+    .unwrap();
+
+    server
+        .post(format!("/event/{}/restore", Uuid::new_v4()).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_not_found();
+    Ok(())
+}
+
+#[tokio::test]
+async fn restore_workspace_of_other_user_is_forbidden() -> Result<()> {
+    let trashed = workspace::Model {
+        deleted_at: deleted(),
+        ..mock_workspace(Uuid::new_v4(), "test", "other_user")
+    };
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![trashed.clone()]]),
+    );
+    let server = TestServer::new(create_router(AppState::new(Arc::new(
+        mock_db.into_connection(),
+    )))?)
+    .unwrap();
+
+    server
+        .post(format!("/workspace/{}/restore", trashed.id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await
+        .assert_status_forbidden();
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_workspace_trash() -> Result<()> {
+    let workspace_id = Uuid::new_v4();
+    let live_event = mock_event(Uuid::new_v4(), "Live", workspace_id);
+    let trashed_event = event::Model {
+        deleted_at: deleted(),
+        ..mock_event(Uuid::new_v4(), "Trashed", workspace_id)
+    };
+    let trashed_section = section::Model {
+        deleted_at: deleted(),
+        ..mock_section(Uuid::new_v4(), "Balcony", live_event.id, "10.00")
+    };
+    let trashed_form = form::Model {
+        deleted_at: deleted(),
+        ..mock_form(Uuid::new_v4(), live_event.id, "Survey", "After the show")
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![mock_workspace(
+            workspace_id,
+            "test",
+            TEST_USER_ID,
+        )]])
+        .append_query_results(vec![vec![trashed_event.clone()]])
+        .append_query_results(vec![vec![trashed_section.clone()]])
+        .append_query_results(vec![vec![trashed_form.clone()]]);
+    let app_state = AppState::new(Arc::new(mock_db.into_connection()));
+    let server = TestServer::new(create_router(app_state.clone())?).unwrap();
+
+    let response = server
+        .get(format!("/workspace/{}/trash", workspace_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&TrashResponse {
+        retention_days: TRASH_RETENTION_DAYS,
+        events: vec![EventResponse::from(trashed_event)],
+        sections: vec![trashed_section],
+        forms: vec![trashed_form],
+    });
+    drop(server);
+
+    let log = Arc::into_inner(app_state.db)
+        .unwrap()
+        .into_transaction_log();
+    // Sections of a trashed event come back with it and are not listed.
+    let sections = &log[log.len() - 2].statements()[0];
+    assert!(
+        sections
+            .sql
+            .contains(r#""section"."deleted_at" IS NOT NULL"#)
+    );
+    assert!(sections.sql.contains(r#""event"."deleted_at" IS NULL"#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_deleted_workspaces() -> Result<()> {
+    let trashed = workspace::Model {
+        deleted_at: deleted(),
+        ..mock_workspace(Uuid::new_v4(), "test", TEST_USER_ID)
+    };
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![vec![trashed.clone()]]);
+    let server = TestServer::new(create_router(AppState::new(Arc::new(
+        mock_db.into_connection(),
+    )))?)
+    .unwrap();
+
+    let response = server
+        .get(format!("/workspaces/{}/trash", TEST_USER_ID).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status_ok();
+    response.assert_json(&vec![WorkspaceResponse::from(trashed)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn purge_deletes_children_before_parents() -> Result<()> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([3, 4, 2, 1, 0].map(|rows_affected| MockExecResult {
+            rows_affected,
+            last_insert_id: 0,
+        }))
+        .into_connection();
+
+    let purged = purge_trash(&db, mock_datetime()).await?;
+
+    assert_eq!(purged, 10);
+    let tables: Vec<_> = db
+        .into_transaction_log()
+        .iter()
+        .map(|transaction| transaction.statements()[0].sql.clone())
+        .collect();
+    for (sql, table) in [&tables[0], &tables[2], &tables[3], &tables[4]]
+        .into_iter()
+        .zip(["form", "section", "event", "workspace"])
+    {
+        assert!(sql.starts_with(&format!(
+            r#"DELETE FROM "{table}" WHERE "{table}"."deleted_at" < $1"#
+        )));
+    }
+    // Unbooked seats of purged sections go first; a section with seats left
+    // stays in the trash.
+    assert!(tables[1].starts_with(
+        r#"DELETE FROM "event_object" WHERE "event_object"."section_id" IN (SELECT "section"."id" FROM "section""#
+    ));
+    assert!(tables[1].contains(r#"NOT EXISTS(SELECT $2 FROM "reservation_item""#));
+    assert!(tables[2].contains(r#"NOT EXISTS(SELECT $2 FROM "event_object""#));
+    // Events that sold seats, and their workspaces, keep their sales history.
+    for sql in &tables[3..] {
+        assert!(sql.contains(r#"NOT EXISTS(SELECT $2 FROM "reservation""#));
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use axum::http::StatusCode;
 use axum_test::TestServer;
 use backend::dto::workspace::{DeleteResponse, RenameRequest, WorkspaceRequest, WorkspaceResponse};
 use eyre::Result;
@@ -8,7 +9,9 @@ use uuid::Uuid;
 
 mod common;
 
-use crate::common::helpers::{create_test_app, mock_workspace};
+use crate::common::helpers::{
+    TEST_TOKEN, authenticated, create_test_app, mock_workspace, not_a_member,
+};
 
 #[tokio::test]
 async fn get_workspaces() -> Result<()> {
@@ -20,14 +23,15 @@ async fn get_workspaces() -> Result<()> {
         mock_workspace(id, "test_1", user_id),
         mock_workspace(id2, "test_2", user_id),
     ];
-    let mock_db =
-        MockDatabase::new(DatabaseBackend::Postgres).append_query_results(vec![mock_data.clone()]);
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![mock_data.clone()]);
 
     let app = create_test_app(mock_db).await?;
     let server = TestServer::new(app).unwrap();
 
     let response = server
         .get(format!("/workspaces/{}", user_id).as_str())
+        .authorization_bearer(TEST_TOKEN)
         .await;
 
     response.assert_status_ok();
@@ -43,7 +47,7 @@ async fn create_workspace() -> Result<()> {
 
     let expected = mock_workspace(id, name, user_id);
 
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
         .append_query_results(vec![vec![expected.clone()]]);
 
     let app = create_test_app(mock_db).await?;
@@ -51,6 +55,7 @@ async fn create_workspace() -> Result<()> {
 
     let response = server
         .post("/workspace")
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(WorkspaceRequest {
             name: name.to_string(),
             owner_id: user_id.to_string(),
@@ -67,9 +72,10 @@ async fn delete_workspace() -> Result<()> {
     let id = Uuid::new_v4();
     let user_id = "user_test_nod_prod";
 
-    let mock_data = vec![mock_workspace(id, "test_1", user_id)];
-    let mock_db =
-        MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(vec![MockExecResult {
+    let mock_data = [mock_workspace(id, "test_1", user_id)];
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+        .append_query_results(vec![mock_data.to_vec()])
+        .append_exec_results(vec![MockExecResult {
             rows_affected: 1,
             last_insert_id: 0, // Not used, but required by the struct
         }]);
@@ -79,6 +85,7 @@ async fn delete_workspace() -> Result<()> {
 
     let response = server
         .delete(format!("/workspace?workspace_id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
         .await;
 
     response.assert_status_ok();
@@ -101,7 +108,7 @@ async fn rename_workspaces() -> Result<()> {
 
     let mock_data = mock_workspace(id, "test_1", user_id);
     let expected = mock_workspace(id, rename, user_id);
-    let mock_db = MockDatabase::new(DatabaseBackend::Postgres)
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres))
         .append_query_results(vec![vec![mock_data.clone()]])
         .append_query_results(vec![vec![expected.clone()]]);
 
@@ -110,6 +117,7 @@ async fn rename_workspaces() -> Result<()> {
 
     let response = server
         .put(format!("/workspace/{}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
         .json(&json!(RenameRequest {
             id,
             name: rename.to_string()
@@ -122,3 +130,40 @@ async fn rename_workspaces() -> Result<()> {
     response.assert_json(&expected);
     Ok(())
 }
+
+#[tokio::test]
+async fn get_workspaces_of_other_user_is_forbidden() -> Result<()> {
+    let mock_db = authenticated(MockDatabase::new(DatabaseBackend::Postgres));
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .get("/workspaces/someone_else")
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_workspace_of_other_user_is_forbidden() -> Result<()> {
+    let id = Uuid::new_v4();
+
+    let mock_db = not_a_member(
+        authenticated(MockDatabase::new(DatabaseBackend::Postgres))
+            .append_query_results(vec![vec![mock_workspace(id, "test_1", "someone_else")]]),
+    );
+
+    let app = create_test_app(mock_db).await?;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .delete(format!("/workspace?workspace_id={}", id).as_str())
+        .authorization_bearer(TEST_TOKEN)
+        .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    Ok(())
+}